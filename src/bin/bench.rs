@@ -1,4 +1,4 @@
-use hash_rings::{carp, consistent, jump, maglev, mpc, rendezvous, weighted_rendezvous};
+use hash_rings::{carp, consistent, jump, maglev, mpc, rendezvous, weighted_rendezvous, HashRing};
 use rand::{Rng, XorShiftRng};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -47,7 +47,7 @@ fn bench_carp() {
     let ring = carp::Ring::new(
         nodes
             .iter()
-            .map(|node| carp::Node::new(&node.0, node.1))
+            .map(|node| carp::Node::new(node.0, node.1))
             .collect(),
     );
 
@@ -86,7 +86,7 @@ fn bench_consistent() {
     }
 
     for node in &nodes {
-        ring.insert_node(node, REPLICAS as usize);
+        ring.insert_node(*node, REPLICAS as usize);
     }
 
     let start = Instant::now();
@@ -99,7 +99,7 @@ fn bench_consistent() {
         print_node_statistic(
             *node,
             REPLICAS as f64 / total_replicas as f64,
-            occ_map[&node] / ITEMS as f64,
+            occ_map[node] / ITEMS as f64,
         );
     }
     print_bench_statistic(start.elapsed());
@@ -110,9 +110,9 @@ fn bench_jump() {
     let mut rng = XorShiftRng::new_unseeded();
 
     let mut occ_map = HashMap::new();
-    let ring = jump::Ring::new(NODES as u32);
+    let ring = jump::Ring::new(NODES);
 
-    for i in 0..NODES as u32 {
+    for i in 0..NODES {
         occ_map.insert(i, 0f64);
     }
 
@@ -122,8 +122,8 @@ fn bench_jump() {
         *occ_map.get_mut(&id).unwrap() += 1.0;
     }
 
-    for i in 0..NODES as u32 {
-        print_node_statistic(u64::from(i), 1.0 / NODES as f64, occ_map[&i] / ITEMS as f64);
+    for i in 0..NODES {
+        print_node_statistic(i, 1.0 / NODES as f64, occ_map[&i] / ITEMS as f64);
     }
     print_bench_statistic(start.elapsed());
 }
@@ -145,7 +145,7 @@ fn bench_maglev() {
         nodes.push(id);
     }
 
-    let ring = maglev::Ring::new(nodes.iter().collect());
+    let ring = maglev::Ring::new(nodes.clone());
 
     let start = Instant::now();
     for _ in 0..ITEMS {
@@ -157,7 +157,7 @@ fn bench_maglev() {
         print_node_statistic(
             *node,
             1.0 / NODES as f64,
-            f64::from(occ_map[&node]) / ITEMS as f64,
+            f64::from(occ_map[node]) / ITEMS as f64,
         );
     }
     print_bench_statistic(start.elapsed());
@@ -181,7 +181,7 @@ fn bench_mpc() {
     }
 
     for node in &nodes {
-        ring.insert_node(node);
+        ring.insert_node(*node, 1);
     }
 
     let start = Instant::now();
@@ -194,7 +194,7 @@ fn bench_mpc() {
         print_node_statistic(
             *node,
             1.0 / NODES as f64,
-            f64::from(occ_map[&node]) / ITEMS as f64,
+            f64::from(occ_map[node]) / ITEMS as f64,
         );
     }
     print_bench_statistic(start.elapsed());
@@ -218,7 +218,7 @@ fn bench_rendezvous() {
     }
 
     for node in &nodes {
-        ring.insert_node(node, 1);
+        ring.insert_node(*node, 1);
     }
 
     let start = Instant::now();
@@ -228,7 +228,7 @@ fn bench_rendezvous() {
     }
 
     for node in &nodes {
-        print_node_statistic(*node, 1.0 / NODES as f64, occ_map[&node] / ITEMS as f64);
+        print_node_statistic(*node, 1.0 / NODES as f64, occ_map[node] / ITEMS as f64);
     }
     print_bench_statistic(start.elapsed());
 }
@@ -255,7 +255,7 @@ fn bench_weighted_rendezvous() {
     }
 
     for node in &nodes {
-        ring.insert_node(&node.0, node.1);
+        ring.insert_node(node.0, node.1);
     }
 
     let start = Instant::now();
@@ -267,13 +267,120 @@ fn bench_weighted_rendezvous() {
     for node in &nodes {
         print_node_statistic(
             node.0,
-            node.1 as f64 / total_weight as f64,
+            node.1 / total_weight,
             occ_map[&node.0] / ITEMS as f64,
         );
     }
     print_bench_statistic(start.elapsed());
 }
 
+fn bench_batch<'a, R, F>(name: &str, ring: &'a R, method: &str, get_nodes_batch: F)
+where
+    R: HashRing,
+    for<'b> R::Node<'b>: PartialEq,
+    F: Fn(&'a R, &[u64]) -> Vec<R::Node<'a>>,
+{
+    println!(
+        "\nBenching {} batch lookups ({} nodes, {} items)",
+        name, NODES, ITEMS,
+    );
+    let mut rng = XorShiftRng::new_unseeded();
+    let points: Vec<u64> = (0..ITEMS).map(|_| rng.next_u64()).collect();
+
+    // Warm up the caches so that neither kind of lookup is measured cold.
+    get_nodes_batch(ring, &points);
+
+    let start = Instant::now();
+    let nodes: Vec<_> = points.iter().map(|point| ring.get_node(point)).collect();
+    let single_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let batch_nodes = get_nodes_batch(ring, &points);
+    let batch_elapsed = start.elapsed();
+
+    assert!(nodes == batch_nodes);
+    println!("\nget_node:");
+    print_bench_statistic(single_elapsed);
+    println!("{}:", method);
+    print_bench_statistic(batch_elapsed);
+    println!(
+        "Speedup:                    {:>10.3}x",
+        single_elapsed.as_secs_f64() / batch_elapsed.as_secs_f64(),
+    );
+}
+
+fn bench_batches() {
+    let mut rng = XorShiftRng::new_unseeded();
+    let nodes: Vec<(u64, f64)> = (0..NODES)
+        .map(|_| (rng.next_u64(), rng.next_f64()))
+        .collect();
+
+    let mut ring = consistent::Ring::new();
+    for node in &nodes {
+        ring.insert_node(node.0, REPLICAS as usize);
+    }
+    bench_batch(
+        "consistent hashing",
+        &ring,
+        "get_nodes_batch",
+        |ring, points| ring.get_nodes_batch(points),
+    );
+
+    let mut ring = rendezvous::Ring::new();
+    for node in &nodes {
+        ring.insert_node(node.0, 1);
+    }
+    bench_batch(
+        "rendezvous hashing",
+        &ring,
+        "get_nodes_batch_prefixed",
+        rendezvous::Ring::get_nodes_batch_prefixed,
+    );
+
+    let mut ring = weighted_rendezvous::Ring::new();
+    for node in &nodes {
+        ring.insert_node(node.0, node.1);
+    }
+    bench_batch(
+        "weighted rendezvous hashing",
+        &ring,
+        "get_nodes_batch",
+        |ring, points| ring.get_nodes_batch(points),
+    );
+}
+
+fn bench_frozen() {
+    println!(
+        "\nBenching frozen consistent hashing lookups ({} nodes, {} replicas, {} items)",
+        NODES, REPLICAS, ITEMS,
+    );
+    let mut rng = XorShiftRng::new_unseeded();
+    let mut ring = consistent::Ring::new();
+    for _ in 0..NODES {
+        ring.insert_node(rng.next_u64(), REPLICAS as usize);
+    }
+    let points: Vec<u64> = (0..ITEMS).map(|_| rng.next_u64()).collect();
+
+    let start = Instant::now();
+    let nodes: Vec<u64> = points.iter().map(|point| *ring.get_node(point)).collect();
+    let ring_elapsed = start.elapsed();
+
+    let ring = ring.freeze();
+    let start = Instant::now();
+    let frozen_nodes: Vec<u64> = points.iter().map(|point| *ring.get_node(point)).collect();
+    let frozen_elapsed = start.elapsed();
+
+    assert!(nodes == frozen_nodes);
+    println!("\nRing::get_node:");
+    print_bench_statistic(ring_elapsed);
+    println!("FrozenRing::get_node:");
+    print_bench_statistic(frozen_elapsed);
+    println!(
+        "Speedup:                    {:>10.3}x",
+        ring_elapsed.as_secs_f64() / frozen_elapsed.as_secs_f64(),
+    );
+}
+
 fn main() {
     bench_carp();
     bench_consistent();
@@ -282,4 +389,6 @@ fn main() {
     bench_mpc();
     bench_rendezvous();
     bench_weighted_rendezvous();
+    bench_batches();
+    bench_frozen();
 }
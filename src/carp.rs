@@ -1,7 +1,10 @@
 //! Hashing ring implemented using the Cache Ring Routing Protocol.
 
 use crate::util;
-use std::collections::hash_map::RandomState;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::f64;
 use std::hash::{BuildHasher, Hash};
 
@@ -10,14 +13,15 @@ use std::hash::{BuildHasher, Hash};
 /// The distribution of points to nodes is proportional to the weights of the nodes. For example, a
 /// node with a weight of 3 will receive approximately three times more points than a node with a
 /// weight of 1.
-pub struct Node<'a, T> {
-    id: &'a T,
+#[derive(Clone)]
+pub struct Node<T> {
+    id: T,
     hash: u64,
     weight: f64,
     relative_weight: f64,
 }
 
-impl<'a, T> Node<'a, T> {
+impl<T> Node<T> {
     /// Constructs a new node with a particular weight associated with it.
     ///
     /// # Examples
@@ -25,9 +29,9 @@ impl<'a, T> Node<'a, T> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let node = Node::new(&"node-1", 1f64);
+    /// let node = Node::new("node-1", 1f64);
     /// ```
-    pub fn new(id: &'a T, weight: f64) -> Self {
+    pub fn new(id: T, weight: f64) -> Self {
         Node {
             id,
             hash: 0,
@@ -35,6 +39,26 @@ impl<'a, T> Node<'a, T> {
             relative_weight: 0f64,
         }
     }
+
+    /// Constructs a new node with a particular weight associated with it.
+    ///
+    /// Returns `Error::InvalidWeight` if the weight is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::Node;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(Node::try_new("node-1", 1f64).is_ok());
+    /// assert_eq!(Node::try_new("node-1", 0f64).err(), Some(Error::InvalidWeight));
+    /// ```
+    pub fn try_new(id: T, weight: f64) -> Result<Self, Error> {
+        if !weight.is_finite() || weight <= 0f64 {
+            return Err(Error::InvalidWeight);
+        }
+        Ok(Self::new(id, weight))
+    }
 }
 
 /// A hashing ring implemented using the Cache Array Routing Protocol.
@@ -52,7 +76,7 @@ impl<'a, T> Node<'a, T> {
 ///
 /// let mut ring = Ring::with_hasher(
 ///     DefaultBuildHasher::default(),
-///     vec![Node::new(&"node-1", 1f64), Node::new(&"node-2", 3f64)],
+///     vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)],
 /// );
 ///
 /// ring.remove_node(&"node-1");
@@ -64,12 +88,13 @@ impl<'a, T> Node<'a, T> {
 /// assert_eq!(iterator.next(), Some((&"node-2", 3f64)));
 /// assert_eq!(iterator.next(), None);
 /// ```
-pub struct Ring<'a, T, H = RandomState> {
-    nodes: Vec<Node<'a, T>>,
+#[derive(Clone)]
+pub struct Ring<T, H = StableBuildHasher> {
+    nodes: Vec<Node<T>>,
     hash_builder: H,
 }
 
-impl<'a, T> Ring<'a, T, RandomState> {
+impl<T> Ring<T, StableBuildHasher> {
     /// Constructs a new, empty `Ring<T>`.
     ///
     /// # Examples
@@ -79,7 +104,7 @@ impl<'a, T> Ring<'a, T, RandomState> {
     ///
     /// let mut ring: Ring<&str> = Ring::new(vec![]);
     /// ```
-    pub fn new(nodes: Vec<Node<'a, T>>) -> Self
+    pub fn new(nodes: Vec<Node<T>>) -> Self
     where
         T: Hash + Ord,
     {
@@ -87,7 +112,7 @@ impl<'a, T> Ring<'a, T, RandomState> {
     }
 }
 
-impl<'a, T, H> Ring<'a, T, H> {
+impl<T, H> Ring<T, H> {
     fn rebalance(&mut self) {
         let mut product = 1f64;
         let len = self.nodes.len() as f64;
@@ -125,20 +150,20 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str, _> = Ring::with_hasher(DefaultBuildHasher::default(), vec![]);
     /// ```
-    pub fn with_hasher(hash_builder: H, mut nodes: Vec<Node<'a, T>>) -> Self
+    pub fn with_hasher(hash_builder: H, mut nodes: Vec<Node<T>>) -> Self
     where
         T: Hash + Ord,
         H: BuildHasher + Default,
     {
         for node in &mut nodes {
-            node.hash = util::gen_hash(&hash_builder, node.id);
+            node.hash = util::gen_hash(&hash_builder, &node.id);
         }
         nodes.reverse();
-        nodes.sort_by_key(|node| node.id);
-        nodes.dedup_by_key(|node| node.id);
+        nodes.sort_by(|n, m| n.id.cmp(&m.id));
+        nodes.dedup_by(|n, m| n.id == m.id);
         nodes.sort_by(|n, m| {
             if (n.weight - m.weight).abs() < f64::EPSILON {
-                n.id.cmp(m.id)
+                n.id.cmp(&m.id)
             } else {
                 n.weight
                     .partial_cmp(&m.weight)
@@ -164,16 +189,16 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let mut ring = Ring::new(vec![Node::new(&"node-1", 1f64)]);
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64)]);
     ///
-    /// ring.insert_node(Node::new(&"node-2", 1f64));
+    /// ring.insert_node(Node::new("node-2", 1f64));
     /// ```
-    pub fn insert_node(&mut self, mut new_node: Node<'a, T>)
+    pub fn insert_node(&mut self, mut new_node: Node<T>)
     where
         T: Hash + Ord,
         H: BuildHasher,
     {
-        new_node.hash = util::gen_hash(&self.hash_builder, new_node.id);
+        new_node.hash = util::gen_hash(&self.hash_builder, &new_node.id);
         if let Some(index) = self.nodes.iter().position(|node| node.id == new_node.id) {
             self.nodes[index] = new_node;
         } else {
@@ -181,7 +206,7 @@ impl<'a, T, H> Ring<'a, T, H> {
         }
         self.nodes.sort_by(|n, m| {
             if (n.weight - m.weight).abs() < f64::EPSILON {
-                n.id.cmp(m.id)
+                n.id.cmp(&m.id)
             } else {
                 n.weight
                     .partial_cmp(&m.weight)
@@ -198,7 +223,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let mut ring = Ring::new(vec![Node::new(&"node-1", 1f64), Node::new(&"node-2", 3f64)]);
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
     ///
     /// ring.remove_node(&"node-2");
     /// ```
@@ -206,12 +231,41 @@ impl<'a, T, H> Ring<'a, T, H> {
     where
         T: Eq,
     {
-        if let Some(index) = self.nodes.iter().position(|node| node.id == id) {
+        if let Some(index) = self.nodes.iter().position(|node| &node.id == id) {
             self.nodes.remove(index);
             self.rebalance();
         }
     }
 
+    /// Removes a node from the ring.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
+    ///
+    /// assert_eq!(ring.try_remove_node(&"node-2"), Ok(()));
+    /// assert_eq!(ring.try_remove_node(&"node-2"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<(), Error>
+    where
+        T: Eq,
+    {
+        let index = self
+            .nodes
+            .iter()
+            .position(|node| &node.id == id)
+            .ok_or(Error::UnknownNode)?;
+        self.nodes.remove(index);
+        self.rebalance();
+        Ok(())
+    }
+
     /// Returns the node associated with a point.
     ///
     /// # Panics
@@ -223,35 +277,166 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let mut ring = Ring::new(vec![Node::new(&"node-1", 1f64)]);
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64)]);
     ///
     /// assert_eq!(ring.get_node(&"point-1"), &"node-1");
     /// ```
-    pub fn get_node<U>(&self, point: &U) -> &'a T
+    pub fn get_node<U>(&self, point: &U) -> &T
     where
         T: Ord,
         U: Hash,
         H: BuildHasher,
     {
-        let point_hash = util::gen_hash(&self.hash_builder, point);
-        self.nodes
-            .iter()
-            .map(|node| {
-                (
-                    util::combine_hash(&self.hash_builder, node.hash, point_hash) as f64
-                        * node.relative_weight,
-                    node.id,
-                )
-            })
-            .max_by(|n, m| {
-                if n == m {
-                    n.1.cmp(m.1)
-                } else {
-                    n.0.partial_cmp(&m.0).expect("Expected all non-NaN floats.")
-                }
-            })
-            .expect("Expected non-empty ring.")
-            .1
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(vec![]);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node(Node::new("node-1", 1f64));
+    /// assert_eq!(ring.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        T: Ord,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are ordered by decreasing score, so the first node is the node returned by
+    /// `get_node`. Fewer than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    ///
+    /// let ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Ord,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, point), n)
+    }
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same
+    /// node as `get_node` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T
+    where
+        T: Ord,
+        H: BuildHasher,
+    {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(vec![]);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node(Node::new("node-1", 1f64));
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error>
+    where
+        T: Ord,
+        H: BuildHasher,
+    {
+        self.get_scores(hash)
+            .max_by(compare_scores)
+            .map(|entry| entry.1)
+            .ok_or(Error::EmptyRing)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::carp::{Node, Ring};
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T>
+    where
+        T: Ord,
+        H: BuildHasher,
+    {
+        let mut scores: Vec<(f64, &T)> = self.get_scores(hash).collect();
+        scores.sort_by(|n, m| compare_scores(m, n));
+        scores.into_iter().take(n).map(|entry| entry.1).collect()
+    }
+
+    fn get_scores(&self, point_hash: u64) -> impl Iterator<Item = (f64, &T)>
+    where
+        H: BuildHasher,
+    {
+        self.nodes.iter().map(move |node| {
+            (
+                util::combine_hash(&self.hash_builder, node.hash, point_hash) as f64
+                    * node.relative_weight,
+                &node.id,
+            )
+        })
     }
 
     /// Returns the number of nodes in the ring.
@@ -261,7 +446,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let mut ring = Ring::new(vec![Node::new(&"node-1", 1f64), Node::new(&"node-2", 3f64)]);
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
     ///
     /// assert_eq!(ring.len(), 2);
     /// ```
@@ -276,7 +461,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let ring: Ring<'_, u32, _> = Ring::new(vec![]);
+    /// let ring: Ring<u32, _> = Ring::new(vec![]);
     ///
     /// assert!(ring.is_empty());
     /// ```
@@ -293,19 +478,28 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// ```
     /// use hash_rings::carp::{Node, Ring};
     ///
-    /// let mut ring = Ring::new(vec![Node::new(&"node-1", 1f64), Node::new(&"node-2", 3f64)]);
+    /// let mut ring = Ring::new(vec![Node::new("node-1", 1f64), Node::new("node-2", 3f64)]);
     ///
     /// let mut iterator = ring.iter();
     /// assert_eq!(iterator.next(), Some((&"node-1", 1f64)));
     /// assert_eq!(iterator.next(), Some((&"node-2", 3f64)));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = (&'a T, f64)> {
-        self.nodes.iter().map(|node| (&*node.id, node.weight))
+    pub fn iter(&self) -> impl Iterator<Item = (&T, f64)> {
+        self.nodes.iter().map(|node| (&node.id, node.weight))
     }
 }
 
-impl<'a, T, H> IntoIterator for &'a Ring<'a, T, H> {
+fn compare_scores<T>(n: &(f64, &T), m: &(f64, &T)) -> Ordering
+where
+    T: Ord,
+{
+    n.0.partial_cmp(&m.0)
+        .expect("Expected all non-NaN floats.")
+        .then_with(|| n.1.cmp(m.1))
+}
+
+impl<'a, T, H> IntoIterator for &'a Ring<T, H> {
     type IntoIter = Box<dyn Iterator<Item = (&'a T, f64)> + 'a>;
     type Item = (&'a T, f64);
 
@@ -314,10 +508,61 @@ impl<'a, T, H> IntoIterator for &'a Ring<'a, T, H> {
     }
 }
 
+/// Serializes the ids and weights of the nodes in the ring. The hash builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for Ring<T, H>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: Vec<(&'a T, f64)>,
+        }
+
+        State {
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and weights of its nodes. The ring is rebuilt with
+/// `H::default()`, so lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for Ring<T, H>
+where
+    T: Deserialize<'de> + Hash + Ord,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            nodes: Vec<(T, f64)>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let nodes = state
+            .nodes
+            .into_iter()
+            .map(|(id, weight)| Node::try_new(id, weight))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(de::Error::custom)?;
+        Ok(Self::with_hasher(H::default(), nodes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Node, Ring};
     use crate::test_util::BuildDefaultHasher;
+    use crate::Error;
 
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
@@ -333,7 +578,7 @@ mod tests {
 
     #[test]
     fn test_size_empty() {
-        let ring: Ring<'_, u32, _> = Ring::with_hasher(BuildDefaultHasher::default(), vec![]);
+        let ring: Ring<u32, _> = Ring::with_hasher(BuildDefaultHasher::default(), vec![]);
         assert!(ring.is_empty());
         assert_eq!(ring.len(), 0);
     }
@@ -342,11 +587,11 @@ mod tests {
     fn test_correct_weights() {
         let ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 0.4), Node::new(&1, 0.4), Node::new(&2, 0.2)],
+            vec![Node::new(0, 0.4), Node::new(1, 0.4), Node::new(2, 0.2)],
         );
-        assert_eq!(ring.nodes[0].id, &2);
-        assert_eq!(ring.nodes[1].id, &0);
-        assert_eq!(ring.nodes[2].id, &1);
+        assert_eq!(ring.nodes[0].id, 2);
+        assert_eq!(ring.nodes[1].id, 0);
+        assert_eq!(ring.nodes[2].id, 1);
         assert_approx_eq!(ring.nodes[0].relative_weight, 0.774_596);
         assert_approx_eq!(ring.nodes[1].relative_weight, 1.000_000);
         assert_approx_eq!(ring.nodes[2].relative_weight, 1.000_000);
@@ -356,22 +601,22 @@ mod tests {
     fn test_new_replace() {
         let ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 0.5), Node::new(&1, 0.1), Node::new(&1, 0.5)],
+            vec![Node::new(0, 0.5), Node::new(1, 0.1), Node::new(1, 0.5)],
         );
 
-        assert_eq!(ring.nodes[0].id, &0);
-        assert_eq!(ring.nodes[1].id, &1);
+        assert_eq!(ring.nodes[0].id, 0);
+        assert_eq!(ring.nodes[1].id, 1);
         assert_approx_eq!(ring.nodes[0].relative_weight, 1.000_000);
         assert_approx_eq!(ring.nodes[1].relative_weight, 1.000_000);
     }
 
     #[test]
     fn test_insert_node() {
-        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), vec![Node::new(&0, 0.5)]);
-        ring.insert_node(Node::new(&1, 0.5));
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), vec![Node::new(0, 0.5)]);
+        ring.insert_node(Node::new(1, 0.5));
 
-        assert_eq!(ring.nodes[0].id, &0);
-        assert_eq!(ring.nodes[1].id, &1);
+        assert_eq!(ring.nodes[0].id, 0);
+        assert_eq!(ring.nodes[1].id, 1);
         assert_approx_eq!(ring.nodes[0].relative_weight, 1.000_000);
         assert_approx_eq!(ring.nodes[1].relative_weight, 1.000_000);
     }
@@ -380,12 +625,12 @@ mod tests {
     fn test_insert_node_replace() {
         let mut ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 0.5), Node::new(&1, 0.1)],
+            vec![Node::new(0, 0.5), Node::new(1, 0.1)],
         );
-        ring.insert_node(Node::new(&1, 0.5));
+        ring.insert_node(Node::new(1, 0.5));
 
-        assert_eq!(ring.nodes[0].id, &0);
-        assert_eq!(ring.nodes[1].id, &1);
+        assert_eq!(ring.nodes[0].id, 0);
+        assert_eq!(ring.nodes[1].id, 1);
         assert_approx_eq!(ring.nodes[0].relative_weight, 1.000_000);
         assert_approx_eq!(ring.nodes[1].relative_weight, 1.000_000);
     }
@@ -394,21 +639,41 @@ mod tests {
     fn test_remove_node() {
         let mut ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 0.5), Node::new(&1, 0.5), Node::new(&2, 0.1)],
+            vec![Node::new(0, 0.5), Node::new(1, 0.5), Node::new(2, 0.1)],
         );
         ring.remove_node(&2);
 
-        assert_eq!(ring.nodes[0].id, &0);
-        assert_eq!(ring.nodes[1].id, &1);
+        assert_eq!(ring.nodes[0].id, 0);
+        assert_eq!(ring.nodes[1].id, 1);
         assert_approx_eq!(ring.nodes[0].relative_weight, 1.000_000);
         assert_approx_eq!(ring.nodes[1].relative_weight, 1.000_000);
     }
 
+    #[test]
+    fn test_try_new_invalid_weight() {
+        assert_eq!(Node::try_new(0, 0f64).err(), Some(Error::InvalidWeight));
+        assert_eq!(Node::try_new(0, -1f64).err(), Some(Error::InvalidWeight));
+        assert_eq!(Node::try_new(0, f64::NAN).err(), Some(Error::InvalidWeight));
+        assert_eq!(
+            Node::try_new(0, f64::INFINITY).err(),
+            Some(Error::InvalidWeight)
+        );
+        assert!(Node::try_new(0, 0.5).is_ok());
+    }
+
+    #[test]
+    fn test_try_remove_node() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), vec![Node::new(0, 0.5)]);
+        assert_eq!(ring.try_remove_node(&1), Err(Error::UnknownNode));
+        assert_eq!(ring.try_remove_node(&0), Ok(()));
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+    }
+
     #[test]
     fn test_get_node() {
         let ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 1.0), Node::new(&1, 1.0)],
+            vec![Node::new(0, 1.0), Node::new(1, 1.0)],
         );
 
         assert_eq!(ring.get_node(&0), &0);
@@ -419,11 +684,38 @@ mod tests {
         assert_eq!(ring.get_node(&5), &1);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let ring = Ring::with_hasher(
+            BuildDefaultHasher::default(),
+            vec![Node::new(0, 0.4), Node::new(1, 0.4), Node::new(2, 0.2)],
+        );
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            ring.iter().collect::<Vec<_>>(),
+            other.iter().collect::<Vec<_>>()
+        );
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_weight() {
+        let json = r#"{"nodes":[[0,-1.0]]}"#;
+        assert!(serde_json::from_str::<Ring<u32, BuildDefaultHasher>>(json).is_err());
+    }
+
     #[test]
     fn test_iter() {
         let ring = Ring::with_hasher(
             BuildDefaultHasher::default(),
-            vec![Node::new(&0, 0.4), Node::new(&1, 0.4), Node::new(&2, 0.2)],
+            vec![Node::new(0, 0.4), Node::new(1, 0.4), Node::new(2, 0.2)],
         );
 
         let mut iterator = ring.iter();
@@ -1,10 +1,14 @@
 //! Hashing ring implemented using consistent hashing.
 
 use crate::util;
-use std::collections::hash_map::RandomState;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{BuildHasher, Hash};
-use std::iter::Iterator;
+use std::iter::{self, Iterator};
 use std::vec::Vec;
 
 /// A hashing ring implemented using consistent hashing.
@@ -15,6 +19,9 @@ use std::vec::Vec;
 /// node with the smallest value that is greater than or equal to the point's value. If such a
 /// node does not exist, then the point maps to the node with the smallest value.
 ///
+/// The placements of this ring are not compatible with other implementations of consistent
+/// hashing. Use `ketama::Ring` to agree with libketama and libmemcached clients.
+///
 /// # Examples
 /// ```
 /// use hash_rings::consistent::Ring;
@@ -25,8 +32,8 @@ use std::vec::Vec;
 ///
 /// let mut ring = Ring::with_hasher(DefaultBuildHasher::default());
 ///
-/// ring.insert_node(&"node-1", 1);
-/// ring.insert_node(&"node-2", 3);
+/// ring.insert_node("node-1", 1);
+/// ring.insert_node("node-2", 3);
 ///
 /// ring.remove_node(&"node-1");
 ///
@@ -37,13 +44,14 @@ use std::vec::Vec;
 /// assert_eq!(iterator.next(), Some((&"node-2", 3)));
 /// assert_eq!(iterator.next(), None);
 /// ```
-pub struct Ring<'a, T, H = RandomState> {
-    nodes: BTreeMap<u64, &'a T>,
-    replicas: HashMap<&'a T, usize>,
+#[derive(Clone)]
+pub struct Ring<T, H = StableBuildHasher> {
+    nodes: BTreeMap<u64, T>,
+    replicas: HashMap<T, usize>,
     hash_builder: H,
 }
 
-impl<'a, T> Ring<'a, T, RandomState> {
+impl<T> Ring<T, StableBuildHasher> {
     /// Constructs a new, empty `Ring<T>`.
     ///
     /// # Examples
@@ -61,7 +69,7 @@ impl<'a, T> Ring<'a, T, RandomState> {
     }
 }
 
-impl<'a, T, H> Ring<'a, T, H> {
+impl<T, H> Ring<T, H> {
     /// Constructs a new, empty `Ring<T>` with a specified hash builder.
     ///
     /// # Examples
@@ -92,7 +100,7 @@ impl<'a, T, H> Ring<'a, T, H> {
             .range(hash..)
             .next()
             .or_else(|| self.nodes.iter().next())
-            .map(|entry| *entry.1)
+            .map(|entry| entry.1)
     }
 
     /// Inserts a node into the ring with a number of replicas.
@@ -109,27 +117,58 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str> = Ring::new();
     ///
     /// // "node-2" will receive three times more points than "node-1"
-    /// ring.insert_node(&"node-1", 1);
-    /// ring.insert_node(&"node-2", 3);
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
     /// ```
-    pub fn insert_node(&mut self, id: &'a T, replicas: usize)
+    pub fn insert_node(&mut self, id: T, replicas: usize)
     where
-        T: Hash + Eq,
+        T: Hash + Eq + Clone,
         H: BuildHasher,
     {
         for i in 0..replicas {
             let hash = util::combine_hash(
                 &self.hash_builder,
-                util::gen_hash(&self.hash_builder, id),
+                util::gen_hash(&self.hash_builder, &id),
                 util::gen_hash(&self.hash_builder, &i),
             );
-            self.nodes.insert(hash, id);
+            self.nodes.insert(hash, id.clone());
         }
         self.replicas.insert(id, replicas);
     }
 
+    /// Inserts a node into the ring with a number of replicas.
+    ///
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// assert_eq!(ring.try_insert_node("node-1", 1), Ok(()));
+    /// assert_eq!(ring.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<(), Error>
+    where
+        T: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        self.insert_node(id, replicas);
+        Ok(())
+    }
+
     /// Removes a node and all its replicas from the ring.
     ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    ///
     /// # Examples
     ///
     /// ```
@@ -137,8 +176,8 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 1);
-    /// ring.insert_node(&"node-2", 1);
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
     /// ring.remove_node(&"node-2");
     /// ```
     pub fn remove_node(&mut self, id: &T)
@@ -146,7 +185,34 @@ impl<'a, T, H> Ring<'a, T, H> {
         T: Hash + Eq,
         H: BuildHasher,
     {
-        for i in 0..self.replicas[id] {
+        if let Err(err) = self.try_remove_node(id) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Removes a node and all its replicas from the ring.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Ok(()));
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<(), Error>
+    where
+        T: Hash + Eq,
+        H: BuildHasher,
+    {
+        let replicas = self.replicas.remove(id).ok_or(Error::UnknownNode)?;
+        for i in 0..replicas {
             let hash = util::combine_hash(
                 &self.hash_builder,
                 util::gen_hash(&self.hash_builder, id),
@@ -154,7 +220,7 @@ impl<'a, T, H> Ring<'a, T, H> {
             );
             let should_remove = {
                 if let Some(existing_id) = self.nodes.get(&hash) {
-                    *existing_id == id
+                    existing_id == id
                 } else {
                     false
                 }
@@ -164,7 +230,7 @@ impl<'a, T, H> Ring<'a, T, H> {
                 self.nodes.remove(&hash);
             }
         }
-        self.replicas.remove(id);
+        Ok(())
     }
 
     /// Returns the node associated with a point.
@@ -180,7 +246,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 1);
+    /// ring.insert_node("node-1", 1);
     /// assert_eq!(ring.get_node(&"point-1"), &"node-1");
     /// ```
     pub fn get_node<U>(&self, point: &U) -> &T
@@ -188,22 +254,219 @@ impl<'a, T, H> Ring<'a, T, H> {
         U: Hash,
         H: BuildHasher,
     {
-        let hash = util::gen_hash(&self.hash_builder, point);
-        match self.get_next_node(hash) {
-            Some(node) => &*node,
-            None => panic!("Error: empty ring."),
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are found by walking clockwise around the ring from the point and skipping the
+    /// replicas of nodes that were already returned, so the first node is the node returned by
+    /// `get_node`. Fewer than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, point), n)
+    }
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same
+    /// node as `get_node` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        self.get_next_node(hash).ok_or(Error::EmptyRing)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+    {
+        let n = cmp::min(n, self.len());
+        let mut seen = HashSet::with_capacity(n);
+        let mut ret = Vec::with_capacity(n);
+        for (_, id) in self.nodes.range(hash..).chain(self.nodes.range(..hash)) {
+            if ret.len() == n {
+                break;
+            }
+            if seen.insert(id) {
+                ret.push(id);
+            }
+        }
+        ret
+    }
+
+    /// Returns the nodes associated with a batch of points in the order of the points.
+    ///
+    /// The hashes of the points are sorted so that the ring is walked once for the whole batch
+    /// instead of searched once for each point, which is faster than calling `get_node` for each
+    /// point when the batch is large relative to the number of replicas in the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let points = ["point-1", "point-2", "point-3"];
+    /// let nodes = ring.get_nodes_batch(&points);
+    /// for (point, node) in points.iter().zip(nodes) {
+    ///     assert_eq!(node, ring.get_node(point));
+    /// }
+    /// ```
+    pub fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let first = match self.nodes.values().next() {
+            Some(id) => id,
+            None => panic!("Error: {}.", Error::EmptyRing),
+        };
+
+        let mut hashes: Vec<(u64, usize)> = points
+            .iter()
+            .map(|point| util::gen_hash(&self.hash_builder, point))
+            .zip(0..)
+            .collect();
+        hashes.sort_unstable();
+
+        // Points with a larger hash than every replica wrap around to the first replica.
+        let mut ret = vec![first; points.len()];
+        let mut nodes = self.nodes.iter().peekable();
+        for (hash, index) in hashes {
+            while nodes.next_if(|entry| *entry.0 < hash).is_some() {}
+            match nodes.peek() {
+                Some(entry) => ret[index] = entry.1,
+                None => break,
+            }
         }
+        ret
     }
 
     fn contains_node(&self, index: u64) -> bool {
         self.nodes.contains_key(&index)
     }
 
-    fn get_replica_count(&self, id: &T) -> usize
+    fn get_replica_count(&self, id: &T) -> Result<usize, Error>
     where
         T: Hash + Eq,
     {
-        self.replicas[id]
+        self.replicas.get(id).cloned().ok_or(Error::UnknownNode)
     }
 
     /// Returns the number of nodes in the ring.
@@ -215,7 +478,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 3);
+    /// ring.insert_node("node-1", 3);
     /// assert_eq!(ring.len(), 1);
     /// ```
     pub fn len(&self) -> usize
@@ -235,7 +498,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str> = Ring::new();
     ///
     /// assert!(ring.is_empty());
-    /// ring.insert_node(&"node-1", 3);
+    /// ring.insert_node("node-1", 3);
     /// assert!(!ring.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool
@@ -254,24 +517,68 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// use hash_rings::consistent::Ring;
     ///
     /// let mut ring = Ring::new();
-    /// ring.insert_node(&"node-1", 1);
+    /// ring.insert_node("node-1", 1);
     ///
     /// let mut iterator = ring.iter();
     /// assert_eq!(iterator.next(), Some((&"node-1", 1)));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = (&'a T, usize)>
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)>
     where
         T: Hash + Eq,
     {
         self.replicas.iter().map(|replica| {
             let (id, replica_count) = replica;
-            (&**id, *replica_count)
+            (id, *replica_count)
         })
     }
+
+    /// Converts the ring into an immutable `FrozenRing` with the same nodes and lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    /// let node = *ring.get_node(&"point-1");
+    ///
+    /// let ring = ring.freeze();
+    /// assert_eq!(ring.get_node(&"point-1"), &node);
+    /// ```
+    pub fn freeze(self) -> FrozenRing<T, H>
+    where
+        T: Hash + Eq,
+    {
+        let (ids, replicas): (Vec<T>, Vec<usize>) = self.replicas.into_iter().unzip();
+        assert!(ids.len() <= u32::MAX as usize);
+        let indices: HashMap<&T, u32> = ids.iter().zip(0..).collect();
+
+        // Visiting the indices of the Eytzinger layout in order assigns them the sorted tokens.
+        let len = self.nodes.len();
+        let mut tokens = vec![0; len + 1];
+        let mut owners = vec![0; len + 1];
+        let mut index = eytzinger_first(len);
+        for (token, id) in &self.nodes {
+            tokens[index] = *token;
+            owners[index] = indices[id];
+            index = eytzinger_next(index, len);
+        }
+        drop(indices);
+
+        FrozenRing {
+            tokens,
+            owners,
+            ids,
+            replicas,
+            hash_builder: self.hash_builder,
+        }
+    }
 }
 
-impl<'a, T, H> IntoIterator for &'a Ring<'a, T, H>
+impl<'a, T, H> IntoIterator for &'a Ring<T, H>
 where
     T: Hash + Eq,
 {
@@ -283,7 +590,7 @@ where
     }
 }
 
-impl<'a, T, H> Default for Ring<'a, T, H>
+impl<T, H> Default for Ring<T, H>
 where
     T: Hash + Eq,
     H: BuildHasher + Default,
@@ -293,74 +600,575 @@ where
     }
 }
 
-/// A client that uses `Ring<T>`.
+/// Serializes the ids and replica counts of the nodes in the ring. The hash builder is not
+/// serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for Ring<T, H>
+where
+    T: Serialize + Hash + Eq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: Vec<(&'a T, usize)>,
+        }
+
+        State {
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and replica counts of its nodes. The ring is rebuilt with
+/// `H::default()`, so lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for Ring<T, H>
+where
+    T: Deserialize<'de> + Hash + Eq + Clone,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            nodes: Vec<(T, usize)>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut ring = Self::default();
+        for (id, replicas) in state.nodes {
+            ring.try_insert_node(id, replicas)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(ring)
+    }
+}
+
+/// An immutable hashing ring implemented using consistent hashing.
+///
+/// `FrozenRing` is constructed with `Ring::freeze` and maps points to the same nodes as the ring
+/// it was frozen from. The hashes of the replicas are stored in a contiguous array in Eytzinger
+/// (breadth-first) order with a parallel array of node indices instead of a `BTreeMap`, so the
+/// first levels of every binary search share cache lines and the search does not branch on the
+/// comparisons. Use `FrozenRing::thaw` to convert it back into a `Ring` to insert or remove nodes.
 ///
 /// # Examples
 /// ```
-/// use hash_rings::consistent::Client;
+/// use hash_rings::consistent::Ring;
 ///
-/// let mut client = Client::new();
-/// client.insert_node(&"node-1", 3);
-/// client.insert_point(&"point-1");
-/// client.insert_point(&"point-2");
+/// let mut ring = Ring::new();
 ///
-/// assert_eq!(client.len(), 1);
-/// assert_eq!(client.get_node(&"point-1"), &"node-1");
+/// ring.insert_node("node-1", 1);
+/// ring.insert_node("node-2", 3);
 ///
-/// client.remove_point(&"point-2");
-/// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
+/// let frozen = ring.freeze();
+/// assert_eq!(frozen.get_node(&"point-1"), &"node-2");
+/// assert_eq!(frozen.len(), 2);
+///
+/// let mut ring = frozen.thaw();
+/// ring.remove_node(&"node-2");
+/// assert_eq!(ring.get_node(&"point-1"), &"node-1");
 /// ```
-pub struct Client<'a, T, U, H = RandomState> {
-    ring: Ring<'a, T, H>,
-    data: BTreeMap<u64, HashSet<&'a U>>,
+#[derive(Clone)]
+pub struct FrozenRing<T, H = StableBuildHasher> {
+    // The replica hashes and owning node indices in Eytzinger order. Index 0 is unused so that the
+    // children of index `k` are `2 * k` and `2 * k + 1`.
+    tokens: Vec<u64>,
+    owners: Vec<u32>,
+    ids: Vec<T>,
+    replicas: Vec<usize>,
+    hash_builder: H,
 }
 
-impl<'a, T, U> Client<'a, T, U, RandomState> {
-    /// Constructs a new, empty `Client<T, U>`.
+// Returns the index of the smallest element of an Eytzinger layout of `len` elements, or 0 if
+// `len` is 0.
+fn eytzinger_first(len: usize) -> usize {
+    (len + 1).next_power_of_two() / 2
+}
+
+// Returns the index of the element after the element at `index` in sorted order in an Eytzinger
+// layout of `len` elements, or 0 if it is the largest element.
+fn eytzinger_next(index: usize, len: usize) -> usize {
+    if 2 * index < len {
+        let mut index = 2 * index + 1;
+        while 2 * index <= len {
+            index *= 2;
+        }
+        index
+    } else {
+        index >> (index.trailing_ones() + 1)
+    }
+}
+
+impl<T, H> FrozenRing<T, H> {
+    fn ring_len(&self) -> usize {
+        self.tokens.len() - 1
+    }
+
+    // Returns the index of the smallest token that is greater than or equal to `hash`, wrapping
+    // around to the smallest token. The search descends to a leaf and then climbs back up past
+    // every right turn, so it only branches on the depth of the tree.
+    fn get_index(&self, hash: u64) -> usize {
+        let len = self.ring_len();
+        let mut index = 1;
+        while index <= len {
+            index = 2 * index + usize::from(self.tokens[index] < hash);
+        }
+        match index >> (index.trailing_ones() + 1) {
+            0 => eytzinger_first(len),
+            index => index,
+        }
+    }
+
+    // Returns the index of the token after the token at `index`, wrapping around to the smallest
+    // token.
+    fn get_next_index(&self, index: usize) -> usize {
+        let len = self.ring_len();
+        match eytzinger_next(index, len) {
+            0 => eytzinger_first(len),
+            index => index,
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::Ring;
     ///
-    /// let mut client: Client<&str, &str> = Client::new();
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.freeze().get_node(&"point-1"), &"node-1");
     /// ```
-    pub fn new() -> Self
+    pub fn get_node<U>(&self, point: &U) -> &T
     where
-        T: Hash + Eq,
-        U: Hash + Eq,
+        U: Hash,
+        H: BuildHasher,
     {
-        Self::default()
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
     }
-}
 
-impl<'a, T, U, H> Client<'a, T, U, H> {
-    /// Constructs a new, empty `Client<T, U>` with a specified hash builder.
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
-    /// use std::collections::hash_map::DefaultHasher;
-    /// use std::hash::BuildHasherDefault;
-    ///
-    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
     ///
-    /// let mut client: Client<&str, &str, _> = Client::with_hasher(DefaultBuildHasher::default());
+    /// let ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.freeze().try_get_node(&"point-1"), Err(Error::EmptyRing));
     /// ```
-    pub fn with_hasher(hash_builder: H) -> Self
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
     where
-        T: Hash + Eq,
-        U: Hash + Eq,
-        H: BuildHasher + Default,
+        U: Hash,
+        H: BuildHasher,
     {
-        Self {
-            ring: Ring::with_hasher(hash_builder),
-            data: BTreeMap::new(),
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are the nodes returned by `Ring::get_nodes`, so the first node is the node
+    /// returned by `get_node`. Fewer than `n` nodes are returned if the ring has fewer than `n`
+    /// nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let ring = ring.freeze();
+    /// let nodes = ring.get_nodes(&"point-1", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, point), n)
+    }
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same
+    /// node as `get_node` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let ring = ring.freeze();
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.freeze().try_get_node_by_hash(1), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        if self.ring_len() == 0 {
+            return Err(Error::EmptyRing);
+        }
+        Ok(&self.ids[self.owners[self.get_index(hash)] as usize])
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let ring = ring.freeze();
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        if self.ring_len() == 0 {
+            return Vec::new();
+        }
+        let n = cmp::min(n, self.ids.len());
+        let mut seen = HashSet::with_capacity(n);
+        let mut ret = Vec::with_capacity(n);
+        let mut index = self.get_index(hash);
+        for _ in 0..self.ring_len() {
+            if ret.len() == n {
+                break;
+            }
+            let owner = self.owners[index];
+            if seen.insert(owner) {
+                ret.push(&self.ids[owner as usize]);
+            }
+            index = self.get_next_index(index);
+        }
+        ret
+    }
+
+    /// Returns the nodes associated with a batch of points in the order of the points.
+    ///
+    /// The hashes of the points are sorted so that the ring is walked once for the whole batch
+    /// instead of searched once for each point, which is faster than calling `get_node` for each
+    /// point when the batch is large relative to the number of replicas in the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let ring = ring.freeze();
+    /// let points = ["point-1", "point-2", "point-3"];
+    /// let nodes = ring.get_nodes_batch(&points);
+    /// for (point, node) in points.iter().zip(nodes) {
+    ///     assert_eq!(node, ring.get_node(point));
+    /// }
+    /// ```
+    pub fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let len = self.ring_len();
+        if len == 0 {
+            panic!("Error: {}.", Error::EmptyRing);
+        }
+
+        let mut hashes: Vec<(u64, usize)> = points
+            .iter()
+            .map(|point| util::gen_hash(&self.hash_builder, point))
+            .zip(0..)
+            .collect();
+        hashes.sort_unstable();
+
+        // Points with a larger hash than every replica wrap around to the first replica.
+        let first = eytzinger_first(len);
+        let mut ret = vec![&self.ids[self.owners[first] as usize]; points.len()];
+        let mut token = first;
+        for (hash, index) in hashes {
+            while token != 0 && self.tokens[token] < hash {
+                token = eytzinger_next(token, len);
+            }
+            if token == 0 {
+                break;
+            }
+            ret[index] = &self.ids[self.owners[token] as usize];
+        }
+        ret
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 3);
+    /// assert_eq!(ring.freeze().len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns `true` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let ring: Ring<&str> = Ring::new();
+    /// assert!(ring.freeze().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield nodes and the replica count in
+    /// no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring = Ring::new();
+    /// ring.insert_node("node-1", 1);
+    ///
+    /// let ring = ring.freeze();
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some((&"node-1", 1)));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.ids.iter().zip(self.replicas.iter().cloned())
+    }
+
+    /// Converts the ring back into a mutable `Ring` with the same nodes and lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// ring.insert_node("node-1", 1);
+    ///
+    /// let mut ring = ring.freeze().thaw();
+    /// ring.insert_node("node-2", 1);
+    /// assert_eq!(ring.len(), 2);
+    /// ```
+    pub fn thaw(self) -> Ring<T, H>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let nodes = self.tokens[1..]
+            .iter()
+            .zip(&self.owners[1..])
+            .map(|(token, owner)| (*token, self.ids[*owner as usize].clone()))
+            .collect();
+        let replicas = self.ids.into_iter().zip(self.replicas).collect();
+
+        Ring {
+            nodes,
+            replicas,
+            hash_builder: self.hash_builder,
+        }
+    }
+}
+
+impl<'a, T, H> IntoIterator for &'a FrozenRing<T, H> {
+    type IntoIter = Box<dyn Iterator<Item = (&'a T, usize)> + 'a>;
+    type Item = (&'a T, usize);
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Serializes the ids and replica counts of the nodes in the ring like `Ring`. The hash builder is
+/// not serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for FrozenRing<T, H>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: Vec<(&'a T, usize)>,
+        }
+
+        State {
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and replica counts of its nodes by deserializing a `Ring` and
+/// freezing it. The ring is rebuilt with `H::default()`, so lookups are only preserved if the
+/// default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for FrozenRing<T, H>
+where
+    T: Deserialize<'de> + Hash + Eq + Clone,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ring::deserialize(deserializer).map(Ring::freeze)
+    }
+}
+
+/// A client that uses `Ring<T>`.
+///
+/// # Examples
+/// ```
+/// use hash_rings::consistent::Client;
+///
+/// let mut client = Client::new();
+/// client.insert_node("node-1", 3);
+/// client.insert_point("point-1");
+/// client.insert_point("point-2");
+///
+/// assert_eq!(client.len(), 1);
+/// assert_eq!(client.get_node(&"point-1"), &"node-1");
+///
+/// client.remove_point(&"point-2");
+/// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
+/// ```
+pub struct Client<T, U, H = StableBuildHasher> {
+    ring: Ring<T, H>,
+    data: BTreeMap<u64, HashSet<U>>,
+}
+
+impl<T, U> Client<T, U, StableBuildHasher> {
+    /// Constructs a new, empty `Client<T, U>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    /// ```
+    pub fn new() -> Self
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        Self::default()
+    }
+}
+
+impl<T, U, H> Client<T, U, H> {
+    /// Constructs a new, empty `Client<T, U>` with a specified hash builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let mut client: Client<&str, &str, _> = Client::with_hasher(DefaultBuildHasher::default());
+    /// ```
+    pub fn with_hasher(hash_builder: H) -> Self
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher + Default,
+    {
+        Self {
+            ring: Ring::with_hasher(hash_builder),
+            data: BTreeMap::new(),
         }
     }
 
-    fn get_next_node(&mut self, hash: u64) -> Option<(u64, &mut HashSet<&'a U>)> {
+    fn get_next_node(&mut self, hash: u64) -> Option<(u64, &mut HashSet<U>)> {
         if self.data.range_mut(hash..).next().is_some() {
             self.data
                 .range_mut(hash..)
@@ -373,7 +1181,8 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
         }
     }
 
-    /// Inserts a node into the ring with a number of replicas.
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
     ///
     /// Increasing the number of replicas will increase the number of expected points mapped to the
     /// node. For example, a node with three replicas will receive approximately three times more
@@ -387,13 +1196,16 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// let mut client: Client<&str, &str> = Client::new();
     ///
     /// // "node-2" will receive three times more points than "node-1"
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_node(&"node-2", 3);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.insert_node("node-2", 3) {
+    ///     assert_eq!((point, from, to), ("point-1", "node-1", "node-2"));
+    /// }
     /// ```
-    pub fn insert_node(&mut self, id: &'a T, replicas: usize)
+    pub fn insert_node(&mut self, id: T, replicas: usize) -> Vec<(U, T, T)>
     where
-        T: Hash + Eq,
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
         let new_hashes = (0..replicas)
@@ -405,10 +1217,22 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
                 )
             })
             .collect::<Vec<u64>>();
-        self.ring.insert_node(id, replicas);
-        for new_hash in new_hashes {
-            // If hash already exists, then no additional work is needed to be done.
-            if self.data.contains_key(&new_hash) {
+        let old_ids = new_hashes
+            .iter()
+            .map(|new_hash| self.ring.nodes.get(new_hash).cloned())
+            .collect::<Vec<Option<T>>>();
+        self.ring.insert_node(id.clone(), replicas);
+        let mut migrations = Vec::new();
+        for (new_hash, old_id) in new_hashes.into_iter().zip(old_ids) {
+            // If hash already exists, then the points of the hash move to the new node.
+            if let Some(points) = self.data.get(&new_hash) {
+                if let Some(old_id) = old_id.filter(|old_id| *old_id != id) {
+                    migrations.extend(
+                        points
+                            .iter()
+                            .map(|point| (point.clone(), old_id.clone(), id.clone())),
+                    );
+                }
                 continue;
             }
             let hash = match self.get_next_node(new_hash) {
@@ -419,7 +1243,7 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
                 }
             };
             let Client { ring, data } = self;
-            let (old_set, new_set) = data
+            let (old_set, new_set): (HashSet<U>, HashSet<U>) = data
                 .get_mut(&hash)
                 .expect("Expected node to exist.")
                 .drain()
@@ -431,52 +1255,151 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
                         new_hash < point_hash || point_hash <= hash
                     }
                 });
+            let old_id = &ring.nodes[&hash];
+            if *old_id != id {
+                migrations.extend(
+                    new_set
+                        .iter()
+                        .map(|point| (point.clone(), old_id.clone(), id.clone())),
+                );
+            }
             self.data.insert(hash, old_set);
             self.data.insert(new_hash, new_set);
         }
+        migrations
     }
 
-    /// Removes a node and all its replicas from the ring.
-    ///
-    /// # Panics
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
     ///
-    /// Panics if the ring is empty after removal of a node or if the node does not exist.
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
     ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_node(&"node-2", 1);
-    /// client.remove_node(&"node-2");
+    /// assert_eq!(client.try_insert_node("node-1", 1), Ok(vec![]));
+    /// assert_eq!(client.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
     /// ```
-    pub fn remove_node(&mut self, id: &T)
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<Vec<(U, T, T)>, Error>
     where
-        T: Hash + Eq,
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        let replicas = self.ring.get_replica_count(id);
-        self.ring.remove_node(id);
-        for i in 0..replicas {
-            let hash = util::combine_hash(
-                &self.ring.hash_builder,
-                util::gen_hash(&self.ring.hash_builder, id),
-                util::gen_hash(&self.ring.hash_builder, &i),
-            );
-            if !self.ring.contains_node(hash) {
-                if let Some(points) = self.data.remove(&hash) {
-                    if let Some((_, next_points)) = self.get_next_node(hash) {
-                        next_points.extend(points);
-                    } else {
-                        panic!("Error: empty ring after deletion.");
-                    }
-                }
-            }
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
         }
+        Ok(self.insert_node(id, replicas))
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty after removal of a node or if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.remove_node(&"node-2") {
+    ///     assert_eq!((point, from, to), ("point-1", "node-2", "node-1"));
+    /// }
+    /// ```
+    pub fn remove_node(&mut self, id: &T) -> Vec<(U, T, T)>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        match self.try_remove_node(id) {
+            Ok(migrations) => migrations,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist and `Error::EmptyRing` if the ring
+    /// would be empty after removal of the node. The client is left unchanged if an error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_remove_node(&"node-3"), Err(Error::UnknownNode));
+    /// assert!(client.try_remove_node(&"node-2").is_ok());
+    /// assert_eq!(client.try_remove_node(&"node-1"), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<Vec<(U, T, T)>, Error>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        let replicas = self.ring.get_replica_count(id)?;
+        if self
+            .ring
+            .iter()
+            .all(|(other_id, other_replicas)| other_id == id || other_replicas == 0)
+        {
+            return Err(Error::EmptyRing);
+        }
+        self.ring.remove_node(id);
+        let mut detached_points = Vec::new();
+        for i in 0..replicas {
+            let hash = util::combine_hash(
+                &self.ring.hash_builder,
+                util::gen_hash(&self.ring.hash_builder, id),
+                util::gen_hash(&self.ring.hash_builder, &i),
+            );
+            if !self.ring.contains_node(hash) {
+                if let Some(points) = self.data.remove(&hash) {
+                    detached_points.push((hash, points));
+                }
+            }
+        }
+        // All replicas of the node are detached first so that points are forwarded directly to
+        // the remaining nodes.
+        let mut migrations = Vec::new();
+        for (hash, points) in detached_points {
+            let next_hash = self
+                .get_next_node(hash)
+                .expect("Expected non-empty ring.")
+                .0;
+            let next_id = &self.ring.nodes[&next_hash];
+            migrations.extend(
+                points
+                    .iter()
+                    .map(|point| (point.clone(), id.clone(), next_id.clone())),
+            );
+            self.data
+                .get_mut(&next_hash)
+                .expect("Expected node to exist.")
+                .extend(points);
+        }
+        Ok(migrations)
     }
 
     /// Returns the points associated with a node and its replicas.
@@ -492,18 +1415,47 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
     /// ```
     pub fn get_points(&self, id: &T) -> Vec<&U>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        match self.try_get_points(id) {
+            Ok(points) => points,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the points associated with a node and its replicas.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_get_points(&"node-1"), Ok(vec![&"point-1"]));
+    /// assert_eq!(client.try_get_points(&"node-2"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_get_points(&self, id: &T) -> Result<Vec<&U>, Error>
     where
         T: Hash + Eq,
         U: Hash + Eq,
         H: BuildHasher,
     {
         let mut ret: Vec<&U> = Vec::new();
-        for i in 0..self.ring.get_replica_count(id) {
+        for i in 0..self.ring.get_replica_count(id)? {
             let hash = util::combine_hash(
                 &self.ring.hash_builder,
                 util::gen_hash(&self.ring.hash_builder, id),
@@ -513,7 +1465,7 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
                 ret.extend(points.iter());
             }
         }
-        ret
+        Ok(ret)
     }
 
     /// Returns the node associated with a point.
@@ -529,19 +1481,1033 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// assert_eq!(client.get_node(&"point-1"), &"node-1");
     /// ```
-    pub fn get_node(&self, point: &U) -> &T
+    pub fn get_node(&self, point: &U) -> &T
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.get_node(point)
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    /// assert_eq!(client.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.try_get_node(point)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference. The first
+    /// node is the node returned by `get_node`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.get_nodes(&"point-1", 2), [&"node-1"]);
+    /// ```
+    pub fn get_nodes(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.get_nodes(point, n)
+    }
+
+    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client = Client::new();
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// ```
+    pub fn insert_point(&mut self, point: U) -> &T
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        match self.try_insert_point(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client = Client::new();
+    /// assert_eq!(client.try_insert_point("point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_insert_point("point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_insert_point(&mut self, point: U) -> Result<&T, Error>
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        let hash = util::gen_hash(&self.ring.hash_builder, &point);
+        let (_, points) = self.get_next_node(hash).ok_or(Error::EmptyRing)?;
+        points.insert(point);
+        Ok(self
+            .ring
+            .get_next_node(hash)
+            .expect("Expected non-empty ring."))
+    }
+
+    /// Removes a point from the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client = Client::new();
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// client.remove_point(&"point-1");
+    /// ```
+    pub fn remove_point(&mut self, point: &U)
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        if let Err(err) = self.try_remove_point(point) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Removes a point from the ring.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    /// assert_eq!(client.try_remove_point(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_remove_point(&"point-1"), Ok(()));
+    /// ```
+    pub fn try_remove_point(&mut self, point: &U) -> Result<(), Error>
+    where
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        let hash = util::gen_hash(&self.ring.hash_builder, &point);
+        let (_, points) = self.get_next_node(hash).ok_or(Error::EmptyRing)?;
+        points.remove(point);
+        Ok(())
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 3);
+    /// assert_eq!(client.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize
+    where
+        T: Hash + Eq,
+    {
+        self.ring.len()
+    }
+
+    /// Returns `true` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// assert!(client.is_empty());
+    /// client.insert_node("node-1", 3);
+    /// assert!(!client.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool
+    where
+        T: Hash + Eq,
+    {
+        self.ring.is_empty()
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield nodes and points in no
+    /// particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Client;
+    ///
+    /// let mut client = Client::new();
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    ///
+    /// let mut iterator = client.iter();
+    /// assert_eq!(iterator.next(), Some((&"node-1", vec![&"point-1"])));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&T, Vec<&U>)>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.iter().map(move |replica| {
+            let mut points = Vec::new();
+            for i in 0..replica.1 {
+                let hash = util::combine_hash(
+                    &self.ring.hash_builder,
+                    util::gen_hash(&self.ring.hash_builder, replica.0),
+                    util::gen_hash(&self.ring.hash_builder, &i),
+                );
+                points.extend(&self.data[&hash])
+            }
+            (replica.0, points)
+        })
+    }
+}
+
+impl<'a, T, U, H> IntoIterator for &'a Client<T, U, H>
+where
+    T: Hash + Eq,
+    U: Hash + Eq,
+    H: BuildHasher,
+{
+    type IntoIter = Box<dyn Iterator<Item = (&'a T, Vec<&'a U>)> + 'a>;
+    type Item = (&'a T, Vec<&'a U>);
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+impl<T, U, H> Default for Client<T, U, H>
+where
+    T: Hash + Eq,
+    U: Hash + Eq,
+    H: BuildHasher + Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(Default::default())
+    }
+}
+
+/// Serializes the ids and replica counts of the nodes in the client and its points. The hash
+/// builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, U, H> Serialize for Client<T, U, H>
+where
+    T: Serialize + Hash + Eq,
+    U: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T, U> {
+            nodes: Vec<(&'a T, usize)>,
+            points: Vec<&'a U>,
+        }
+
+        State {
+            nodes: self.ring.iter().collect(),
+            points: self.data.values().flatten().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a client from the ids and replica counts of its nodes and its points. The client
+/// is rebuilt with `H::default()`, so lookups and the assignment of points to nodes are only
+/// preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, U, H> Deserialize<'de> for Client<T, U, H>
+where
+    T: Deserialize<'de> + Hash + Eq + Clone,
+    U: Deserialize<'de> + Hash + Eq + Clone,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T, U> {
+            nodes: Vec<(T, usize)>,
+            points: Vec<U>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut client = Self::default();
+        for (id, replicas) in state.nodes {
+            client
+                .try_insert_node(id, replicas)
+                .map_err(de::Error::custom)?;
+        }
+        for point in state.points {
+            client.try_insert_point(point).map_err(de::Error::custom)?;
+        }
+        Ok(client)
+    }
+}
+
+/// A client that uses `Ring<T>` and bounds the number of points associated with each node.
+///
+/// Consistent hashing with bounded loads caps every node at `ceil((1 + epsilon) * m / n)` points,
+/// where `m` is the number of points and `n` is the number of nodes. A point is mapped to the
+/// first node clockwise from the point that is not full, so points that would overflow a node are
+/// forwarded clockwise to the next node with spare capacity. The bound is maintained when points
+/// and nodes are inserted and removed by forwarding the excess points of nodes that exceed the
+/// capacity.
+///
+/// # Examples
+/// ```
+/// use hash_rings::consistent::BoundedClient;
+///
+/// let mut client = BoundedClient::new(0.25);
+/// client.insert_node("node-1", 10);
+/// client.insert_node("node-2", 10);
+/// for point in 0..100 {
+///     client.insert_point(point);
+/// }
+///
+/// assert_eq!(client.capacity(), 63);
+/// assert!(client.get_points(&"node-1").len() <= 63);
+/// assert!(client.get_points(&"node-2").len() <= 63);
+/// ```
+pub struct BoundedClient<T, U, H = StableBuildHasher> {
+    ring: Ring<T, H>,
+    epsilon: f64,
+    nodes: HashMap<T, HashSet<U>>,
+    points: HashMap<U, T>,
+}
+
+impl<T, U> BoundedClient<T, U, StableBuildHasher> {
+    /// Constructs a new, empty `BoundedClient<T, U>` whose nodes are associated with at most a
+    /// `1 + epsilon` factor of the average number of points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    /// ```
+    pub fn new(epsilon: f64) -> Self
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        Self::with_hasher(Default::default(), epsilon)
+    }
+
+    /// Constructs a new, empty `BoundedClient<T, U>` whose nodes are associated with at most a
+    /// `1 + epsilon` factor of the average number of points.
+    ///
+    /// Returns `Error::InvalidCapacity` if `epsilon` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(BoundedClient::<&str, &str>::try_new(0.25).is_ok());
+    /// assert_eq!(
+    ///     BoundedClient::<&str, &str>::try_new(-1f64).err(),
+    ///     Some(Error::InvalidCapacity),
+    /// );
+    /// ```
+    pub fn try_new(epsilon: f64) -> Result<Self, Error>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        Self::try_with_hasher(Default::default(), epsilon)
+    }
+}
+
+impl<T, U, H> BoundedClient<T, U, H> {
+    /// Constructs a new, empty `BoundedClient<T, U>` with a specified hash builder whose nodes are
+    /// associated with at most a `1 + epsilon` factor of the average number of points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let mut client: BoundedClient<&str, &str, _> =
+    ///     BoundedClient::with_hasher(DefaultBuildHasher::default(), 0.25);
+    /// ```
+    pub fn with_hasher(hash_builder: H, epsilon: f64) -> Self
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        assert!(epsilon.is_finite() && epsilon >= 0f64);
+        Self {
+            ring: Ring {
+                nodes: BTreeMap::new(),
+                replicas: HashMap::new(),
+                hash_builder,
+            },
+            epsilon,
+            nodes: HashMap::new(),
+            points: HashMap::new(),
+        }
+    }
+
+    /// Constructs a new, empty `BoundedClient<T, U>` with a specified hash builder whose nodes are
+    /// associated with at most a `1 + epsilon` factor of the average number of points.
+    ///
+    /// Returns `Error::InvalidCapacity` if `epsilon` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::{Error, StableBuildHasher};
+    ///
+    /// let client: Result<BoundedClient<&str, &str, _>, _> =
+    ///     BoundedClient::try_with_hasher(StableBuildHasher::new(), f64::NAN);
+    /// assert_eq!(client.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_hasher(hash_builder: H, epsilon: f64) -> Result<Self, Error>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        if !epsilon.is_finite() || epsilon < 0f64 {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self::with_hasher(hash_builder, epsilon))
+    }
+
+    fn get_capacity(&self, points: usize) -> usize {
+        if self.nodes.is_empty() {
+            return 0;
+        }
+        ((1f64 + self.epsilon) * points as f64 / self.nodes.len() as f64).ceil() as usize
+    }
+
+    // Returns the hash of the first replica clockwise from `hash` whose node has fewer than
+    // `capacity` points.
+    fn get_next_open_node(&self, hash: u64, capacity: usize) -> Option<u64>
+    where
+        T: Hash + Eq,
+    {
+        self.ring
+            .nodes
+            .range(hash..)
+            .chain(self.ring.nodes.range(..hash))
+            .find(|(_, id)| self.nodes[*id].len() < capacity)
+            .map(|(node_hash, _)| *node_hash)
+    }
+
+    // Returns the clockwise distance from `hash` to the first replica of a node.
+    fn get_distance(&self, hash: u64, id: &T) -> u64
+    where
+        T: Eq,
+    {
+        self.ring
+            .nodes
+            .range(hash..)
+            .chain(self.ring.nodes.range(..hash))
+            .find(|(_, other_id)| *other_id == id)
+            .map(|(node_hash, _)| node_hash.wrapping_sub(hash))
+            .expect("Expected node to exist.")
+    }
+
+    // Sorts points by their hashes so that they are placed in the same order in every process.
+    fn sort_points(&self, points: &mut [U])
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        points.sort_by_cached_key(|point| util::gen_hash(&self.ring.hash_builder, point));
+    }
+
+    // Associates a point with the first node clockwise that is not full and returns the node.
+    fn place_point(&mut self, point: U, capacity: usize) -> T
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        let hash = util::gen_hash(&self.ring.hash_builder, &point);
+        let node_hash = self
+            .get_next_open_node(hash, capacity)
+            .expect("Expected a node that is not full.");
+        let id = &self.ring.nodes[&node_hash];
+        self.nodes
+            .get_mut(id)
+            .expect("Expected node to exist.")
+            .insert(point.clone());
+        self.points.insert(point, id.clone());
+        id.clone()
+    }
+
+    // Forwards the excess points of nodes that exceed the capacity and returns the points that
+    // moved. The points that are farthest clockwise from a full node are evicted from it, and the
+    // evicted points are forwarded in order of their hashes, so the result does not depend on the
+    // iteration order of the client's maps.
+    fn enforce_capacity(&mut self) -> Vec<(U, T, T)>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        let capacity = self.get_capacity(self.points.len());
+        let full_ids: Vec<T> = self
+            .nodes
+            .iter()
+            .filter(|(_, points)| points.len() > capacity)
+            .map(|(id, _)| id.clone())
+            .collect();
+        let mut excess_points = Vec::new();
+        for id in full_ids {
+            let mut points: Vec<(u64, u64, U)> = self.nodes[&id]
+                .iter()
+                .map(|point| {
+                    let hash = util::gen_hash(&self.ring.hash_builder, point);
+                    (self.get_distance(hash, &id), hash, point.clone())
+                })
+                .collect();
+            points.sort_unstable_by_key(|entry| (entry.0, entry.1));
+            let node_points = self.nodes.get_mut(&id).expect("Expected node to exist.");
+            for (_, hash, point) in points.drain(capacity..) {
+                node_points.remove(&point);
+                excess_points.push((hash, point, id.clone()));
+            }
+        }
+        excess_points.sort_unstable_by_key(|entry| entry.0);
+        excess_points
+            .into_iter()
+            .map(|(_, point, from)| (point, from))
+            .map(|(point, from)| {
+                let to = self.place_point(point.clone(), capacity);
+                (point, from, to)
+            })
+            .collect()
+    }
+
+    // Combines consecutive moves of the same point and drops points that end up on their original
+    // node.
+    fn merge_migrations(migrations: Vec<(U, T, T)>) -> Vec<(U, T, T)>
+    where
+        T: Eq,
+        U: Hash + Eq,
+    {
+        let mut merged: HashMap<U, (T, T)> = HashMap::new();
+        for (point, from, to) in migrations {
+            match merged.entry(point) {
+                Entry::Occupied(mut entry) => entry.get_mut().1 = to,
+                Entry::Vacant(entry) => {
+                    entry.insert((from, to));
+                }
+            }
+        }
+        merged
+            .into_iter()
+            .filter(|(_, (from, to))| from != to)
+            .map(|(point, (from, to))| (point, from, to))
+            .collect()
+    }
+
+    // Removes a node and returns the points that were associated with it in order of their
+    // hashes.
+    fn detach_node(&mut self, id: &T) -> Vec<U>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        match self.nodes.remove(id) {
+            Some(points) => {
+                self.ring.remove_node(id);
+                for point in &points {
+                    self.points.remove(point);
+                }
+                let mut points: Vec<U> = points.into_iter().collect();
+                self.sort_points(&mut points);
+                points
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order. If the node already exists, its number
+    /// of replicas is replaced.
+    ///
+    /// Points that are closer to the replicas of the new node than to their current node are moved
+    /// to the new node while it has spare capacity. Points of nodes that exceed the reduced
+    /// capacity are then forwarded clockwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// // "node-2" will be closer to three times more points than "node-1"
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.insert_node("node-2", 3) {
+    ///     assert_eq!((point, from, to), ("point-1", "node-1", "node-2"));
+    /// }
+    /// ```
+    pub fn insert_node(&mut self, id: T, replicas: usize) -> Vec<(U, T, T)>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        match self.try_insert_node(id, replicas) {
+            Ok(migrations) => migrations,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order. If the node already exists, its number
+    /// of replicas is replaced.
+    ///
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// assert_eq!(client.try_insert_node("node-1", 1), Ok(vec![]));
+    /// assert_eq!(client.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<Vec<(U, T, T)>, Error>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        let mut migrations = Vec::new();
+        let detached_points = self.detach_node(&id);
+        self.ring.insert_node(id.clone(), replicas);
+        self.nodes.insert(id.clone(), HashSet::new());
+
+        let capacity = self.get_capacity(self.points.len() + detached_points.len());
+        let mut closer_points = Vec::new();
+        for (point, current_id) in &self.points {
+            let hash = util::gen_hash(&self.ring.hash_builder, point);
+            let closest = self
+                .ring
+                .nodes
+                .range(hash..)
+                .chain(self.ring.nodes.range(..hash))
+                .find(|(_, other_id)| *other_id == &id || *other_id == current_id);
+            if let Some((node_hash, other_id)) = closest {
+                if other_id == &id {
+                    closer_points.push((node_hash.wrapping_sub(hash), hash, point.clone()));
+                }
+            }
+        }
+        closer_points.sort_unstable_by_key(|entry| (entry.0, entry.1));
+        for (_, _, point) in closer_points.into_iter().take(capacity) {
+            let current_id = self
+                .points
+                .insert(point.clone(), id.clone())
+                .expect("Expected point to exist.");
+            if let Some(points) = self.nodes.get_mut(&current_id) {
+                points.remove(&point);
+            }
+            if let Some(points) = self.nodes.get_mut(&id) {
+                points.insert(point.clone());
+            }
+            migrations.push((point, current_id, id.clone()));
+        }
+
+        for point in detached_points {
+            let to = self.place_point(point.clone(), capacity);
+            migrations.push((point, id.clone(), to));
+        }
+        migrations.extend(self.enforce_capacity());
+        Ok(Self::merge_migrations(migrations))
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order. The points of the node are forwarded to
+    /// the next node clockwise that is not full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty after removal of a node or if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.remove_node(&"node-2") {
+    ///     assert_eq!((point, from, to), ("point-1", "node-2", "node-1"));
+    /// }
+    /// ```
+    pub fn remove_node(&mut self, id: &T) -> Vec<(U, T, T)>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        match self.try_remove_node(id) {
+            Ok(migrations) => migrations,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order. The points of the node are forwarded to
+    /// the next node clockwise that is not full.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist and `Error::EmptyRing` if the ring
+    /// would be empty after removal of the node. The client is left unchanged if an error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// assert_eq!(client.try_remove_node(&"node-3"), Err(Error::UnknownNode));
+    /// assert_eq!(client.try_remove_node(&"node-2"), Ok(vec![]));
+    /// assert_eq!(client.try_remove_node(&"node-1"), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<Vec<(U, T, T)>, Error>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if !self.nodes.contains_key(id) {
+            return Err(Error::UnknownNode);
+        }
+        if self.nodes.len() == 1 {
+            return Err(Error::EmptyRing);
+        }
+        let detached_points = self.detach_node(id);
+        let capacity = self.get_capacity(self.points.len() + detached_points.len());
+        Ok(detached_points
+            .into_iter()
+            .map(|point| {
+                let to = self.place_point(point.clone(), capacity);
+                (point, id.clone(), to)
+            })
+            .collect())
+    }
+
+    /// Returns the points associated with a node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
+    /// ```
+    pub fn get_points(&self, id: &T) -> Vec<&U>
+    where
+        T: Hash + Eq,
+    {
+        match self.try_get_points(id) {
+            Ok(points) => points,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the points associated with a node.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_get_points(&"node-1"), Ok(vec![&"point-1"]));
+    /// assert_eq!(client.try_get_points(&"node-2"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_get_points(&self, id: &T) -> Result<Vec<&U>, Error>
+    where
+        T: Hash + Eq,
+    {
+        self.nodes
+            .get(id)
+            .map(|points| points.iter().collect())
+            .ok_or(Error::UnknownNode)
+    }
+
+    /// Returns the node associated with a point. If the point has not been inserted, the node that
+    /// the point would be associated with if it were inserted is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.get_node(&"point-1"), &"node-1");
+    /// ```
+    pub fn get_node(&self, point: &U) -> &T
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point. If the point has not been inserted, the node that
+    /// the point would be associated with if it were inserted is returned.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    /// assert_eq!(client.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node(&self, point: &U) -> Result<&T, Error>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        if let Some(id) = self.points.get(point) {
+            return Ok(id);
+        }
+        let hash = util::gen_hash(&self.ring.hash_builder, point);
+        let capacity = self.get_capacity(self.points.len() + 1);
+        self.get_next_open_node(hash, capacity)
+            .map(|node_hash| &self.ring.nodes[&node_hash])
+            .ok_or(Error::EmptyRing)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference. The first
+    /// node is the node returned by `get_node` and the remaining nodes are in the order returned
+    /// by `Ring::get_nodes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.get_nodes(&"point-1", 2), [&"node-1"]);
+    /// ```
+    pub fn get_nodes(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        let first = match self.try_get_node(point) {
+            Ok(first) => first,
+            Err(_) => return Vec::new(),
+        };
+        iter::once(first)
+            .chain(
+                self.ring
+                    .get_nodes(point, self.len())
+                    .into_iter()
+                    .filter(|id| *id != first),
+            )
+            .take(n)
+            .collect()
+    }
+
+    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client = BoundedClient::new(0.25);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// ```
+    pub fn insert_point(&mut self, point: U) -> &T
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        match self.try_insert_point(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client = BoundedClient::new(0.25);
+    /// assert_eq!(client.try_insert_point("point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_insert_point("point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_insert_point(&mut self, point: U) -> Result<&T, Error>
     where
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        self.ring.get_node(point)
+        if self.nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        if !self.points.contains_key(&point) {
+            let capacity = self.get_capacity(self.points.len() + 1);
+            self.place_point(point.clone(), capacity);
+        }
+        Ok(&self.points[&point])
     }
 
-    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    /// Removes a point from the ring and returns the points that moved as `(point, from, to)`
+    /// tuples in no particular order. The points of nodes that exceed the reduced capacity are
+    /// forwarded clockwise.
     ///
     /// # Panics
     ///
@@ -550,72 +2516,98 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::BoundedClient;
     ///
-    /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// let mut client = BoundedClient::new(0.25);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.remove_point(&"point-1"), vec![]);
     /// ```
-    pub fn insert_point(&mut self, point: &'a U) -> &T
+    pub fn remove_point(&mut self, point: &U) -> Vec<(U, T, T)>
     where
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        let hash = util::gen_hash(&self.ring.hash_builder, point);
-        if let Some((_, points)) = self.get_next_node(hash) {
-            points.insert(point);
-            self.ring.get_node(point)
-        } else {
-            panic!("Error: empty ring.");
+        match self.try_remove_point(point) {
+            Ok(migrations) => migrations,
+            Err(err) => panic!("Error: {}.", err),
         }
     }
 
-    /// Removes a point from the ring.
+    /// Removes a point from the ring and returns the points that moved as `(point, from, to)`
+    /// tuples in no particular order. The points of nodes that exceed the reduced capacity are
+    /// forwarded clockwise.
     ///
-    /// # Panics
-    ///
-    /// Panics if the ring is empty.
+    /// Returns `Error::EmptyRing` if the ring is empty.
     ///
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::BoundedClient;
+    /// use hash_rings::Error;
     ///
-    /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
-    /// client.remove_point(&"point-1");
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
+    /// assert_eq!(client.try_remove_point(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_remove_point(&"point-1"), Ok(vec![]));
     /// ```
-    pub fn remove_point(&mut self, point: &U)
+    pub fn try_remove_point(&mut self, point: &U) -> Result<Vec<(U, T, T)>, Error>
     where
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        let hash = util::gen_hash(&self.ring.hash_builder, &point);
-        if let Some((_, points)) = self.get_next_node(hash) {
-            points.remove(point);
-        } else {
-            panic!("Error: empty ring.");
+        if self.nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        match self.points.remove(point) {
+            Some(id) => {
+                if let Some(points) = self.nodes.get_mut(&id) {
+                    points.remove(point);
+                }
+                Ok(self.enforce_capacity())
+            }
+            None => Ok(Vec::new()),
         }
     }
 
+    /// Returns the maximum number of points that can be associated with a node, which is
+    /// `ceil((1 + epsilon) * m / n)` for `m` points and `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::BoundedClient;
+    ///
+    /// let mut client = BoundedClient::new(0.5);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// client.insert_point("point-1");
+    /// client.insert_point("point-2");
+    ///
+    /// assert_eq!(client.capacity(), 2);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.get_capacity(self.points.len())
+    }
+
     /// Returns the number of nodes in the ring.
     ///
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::BoundedClient;
     ///
-    /// let mut client: Client<&str, &str> = Client::new();
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
     ///
-    /// client.insert_node(&"node-1", 3);
+    /// client.insert_node("node-1", 3);
     /// assert_eq!(client.len(), 1);
     /// ```
-    pub fn len(&self) -> usize
-    where
-        T: Hash + Eq,
-    {
-        self.ring.len()
+    pub fn len(&self) -> usize {
+        self.nodes.len()
     }
 
     /// Returns `true` if the ring is empty.
@@ -623,19 +2615,16 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::BoundedClient;
     ///
-    /// let mut client: Client<&str, &str> = Client::new();
+    /// let mut client: BoundedClient<&str, &str> = BoundedClient::new(0.25);
     ///
     /// assert!(client.is_empty());
-    /// client.insert_node(&"node-1", 3);
+    /// client.insert_node("node-1", 3);
     /// assert!(!client.is_empty());
     /// ```
-    pub fn is_empty(&self) -> bool
-    where
-        T: Hash + Eq,
-    {
-        self.ring.is_empty()
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
     }
 
     /// Returns an iterator over the ring. The iterator will yield nodes and points in no
@@ -644,43 +2633,24 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// # Examples
     ///
     /// ```
-    /// use hash_rings::consistent::Client;
+    /// use hash_rings::consistent::BoundedClient;
     ///
-    /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// let mut client = BoundedClient::new(0.25);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     ///
     /// let mut iterator = client.iter();
     /// assert_eq!(iterator.next(), Some((&"node-1", vec![&"point-1"])));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = (&'a T, Vec<&'a U>)>
-    where
-        T: Hash + Eq,
-        U: Hash + Eq,
-        H: BuildHasher,
-    {
-        self.ring.iter().map(move |replica| {
-            let mut points = Vec::new();
-            for i in 0..replica.1 {
-                let hash = util::combine_hash(
-                    &self.ring.hash_builder,
-                    util::gen_hash(&self.ring.hash_builder, &*replica.0),
-                    util::gen_hash(&self.ring.hash_builder, &i),
-                );
-                points.extend(&self.data[&hash])
-            }
-            (replica.0, points)
-        })
+    pub fn iter(&self) -> impl Iterator<Item = (&T, Vec<&U>)> {
+        self.nodes
+            .iter()
+            .map(|(id, points)| (id, points.iter().collect()))
     }
 }
 
-impl<'a, T, U, H> IntoIterator for &'a Client<'a, T, U, H>
-where
-    T: Hash + Eq,
-    U: Hash + Eq,
-    H: BuildHasher,
-{
+impl<'a, T, U, H> IntoIterator for &'a BoundedClient<T, U, H> {
     type IntoIter = Box<dyn Iterator<Item = (&'a T, Vec<&'a U>)> + 'a>;
     type Item = (&'a T, Vec<&'a U>);
 
@@ -689,26 +2659,87 @@ where
     }
 }
 
-impl<'a, T, U, H> Default for Client<'a, T, U, H>
+/// Serializes `epsilon`, the ids and replica counts of the nodes in the client, and its points
+/// with their nodes. The hash builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, U, H> Serialize for BoundedClient<T, U, H>
 where
-    T: Hash + Eq,
-    U: Hash + Eq,
+    T: Serialize + Hash + Eq,
+    U: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T, U> {
+            epsilon: f64,
+            nodes: Vec<(&'a T, usize)>,
+            points: Vec<(&'a U, &'a T)>,
+        }
+
+        State {
+            epsilon: self.epsilon,
+            nodes: self.ring.iter().collect(),
+            points: self.points.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a client from `epsilon`, the ids and replica counts of its nodes, and its points
+/// with their nodes. Since the points are restored to their serialized nodes, the assignment of
+/// points to nodes is preserved. The client is rebuilt with `H::default()`, so lookups of points
+/// that are not in the client are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, U, H> Deserialize<'de> for BoundedClient<T, U, H>
+where
+    T: Deserialize<'de> + Hash + Eq + Clone,
+    U: Deserialize<'de> + Hash + Eq + Clone,
     H: BuildHasher + Default,
 {
-    fn default() -> Self {
-        Self::with_hasher(Default::default())
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T, U> {
+            epsilon: f64,
+            nodes: Vec<(T, usize)>,
+            points: Vec<(U, T)>,
+        }
+
+        let state: State<T, U> = State::deserialize(deserializer)?;
+        let mut client =
+            Self::try_with_hasher(H::default(), state.epsilon).map_err(de::Error::custom)?;
+        for (id, replicas) in state.nodes {
+            client
+                .try_insert_node(id, replicas)
+                .map_err(de::Error::custom)?;
+        }
+        for (point, id) in state.points {
+            client
+                .nodes
+                .get_mut(&id)
+                .ok_or_else(|| de::Error::custom(Error::UnknownNode))?
+                .insert(point.clone());
+            client.points.insert(point, id);
+        }
+        client.enforce_capacity();
+        Ok(client)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Client;
-    use crate::test_util::{BuildAddHasher, BuildDefaultHasher};
+    use super::{eytzinger_first, eytzinger_next, BoundedClient, Client, FrozenRing, Ring};
+    use crate::test_util::{self, BuildAddHasher, BuildDefaultHasher};
+    use crate::Error;
     use std::hash::{Hash, Hasher};
 
     #[test]
     fn test_size_empty() {
-        let client: Client<'_, u32, u32> = Client::new();
+        let client: Client<u32, u32> = Client::new();
         assert!(client.is_empty());
         assert_eq!(client.len(), 0);
     }
@@ -716,39 +2747,67 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_panic_remove_node_empty_client() {
-        let mut client: Client<'_, u32, u32> = Client::new();
-        client.insert_node(&0, 1);
+        let mut client: Client<u32, u32> = Client::new();
+        client.insert_node(0, 1);
         client.remove_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_remove_node_non_existent_node() {
-        let mut client: Client<'_, u32, u32> = Client::new();
+        let mut client: Client<u32, u32> = Client::new();
         client.remove_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_get_node_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         client.get_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_insert_point_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_point(0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_remove_point_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         client.remove_point(&0);
     }
 
+    #[test]
+    fn test_try_empty_client() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        assert_eq!(client.try_get_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_insert_point(0), Err(Error::EmptyRing));
+        assert_eq!(client.try_remove_point(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_get_points(&0), Err(Error::UnknownNode));
+        assert_eq!(client.try_insert_node(0, 0), Err(Error::InvalidWeight));
+        assert!(client.is_empty());
+    }
+
+    #[test]
+    fn test_try_remove_node() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        assert_eq!(client.try_remove_node(&0), Err(Error::UnknownNode));
+
+        client.insert_node(0, 1);
+        client.insert_point(0);
+        assert_eq!(client.try_remove_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.get_points(&0), [&0u32]);
+
+        assert_eq!(client.insert_node(1, 1), [(0, 0, 1)]);
+        assert_eq!(client.try_remove_node(&0), Ok(vec![]));
+        assert_eq!(client.try_get_points(&0), Err(Error::UnknownNode));
+        assert_eq!(client.get_points(&1), [&0u32]);
+    }
+
+    #[derive(Clone)]
     pub struct Key(pub u32);
     impl Hash for Key {
         fn hash<H>(&self, state: &mut H)
@@ -769,58 +2828,246 @@ mod tests {
 
     #[test]
     fn test_insert_node_same_node() {
-        let mut client: Client<'_, Key, u32, BuildAddHasher> = Client::default();
-        client.insert_node(&Key(0), 1);
-        client.insert_point(&0);
-        client.insert_node(&Key(1), 1);
+        let mut client: Client<Key, u32, BuildAddHasher> = Client::default();
+        client.insert_node(Key(0), 1);
+        client.insert_point(0);
+        client.insert_node(Key(1), 1);
         assert_eq!(client.get_points(&Key(0)), [&0u32]);
         assert_eq!(client.get_points(&Key(1)), [&0u32]);
     }
 
     #[test]
     fn test_insert_node_share_node() {
-        let mut client: Client<'_, Key, u32, BuildAddHasher> = Client::default();
-        client.insert_node(&Key(0), 1);
-        client.insert_point(&0);
-        client.insert_point(&1);
+        let mut client: Client<Key, u32, BuildAddHasher> = Client::default();
+        client.insert_node(Key(0), 1);
+        client.insert_point(0);
+        client.insert_point(1);
         let mut actual: Vec<&u32> = client.get_points(&Key(0));
         actual.sort();
         assert_eq!(actual, [&0u32, &1u32]);
-        client.insert_node(&Key(2), 1);
+        client.insert_node(Key(2), 1);
         assert_eq!(client.get_points(&Key(0)), [&0u32]);
         assert_eq!(client.get_points(&Key(2)), [&1u32]);
     }
 
     #[test]
     fn test_remove_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 1);
-        client.insert_point(&0);
-        client.insert_node(&1, 1);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 1);
+        client.insert_point(0);
+        client.insert_node(1, 1);
         client.remove_node(&1);
         assert_eq!(client.get_points(&0), [&0]);
     }
 
     #[test]
     fn test_get_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
         assert_eq!(client.get_node(&0), &0);
     }
 
+    #[test]
+    fn test_get_nodes() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        client.insert_node(2, 10);
+        for point in 0..100 {
+            let mut nodes = client.get_nodes(&point, 4);
+            assert_eq!(nodes[0], client.get_node(&point));
+            assert_eq!(client.get_nodes(&point, 2)[..], nodes[..2]);
+            nodes.sort();
+            assert_eq!(nodes, [&0, &1, &2]);
+        }
+    }
+
+    #[test]
+    fn test_ring_get_nodes_batch() {
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 1);
+        ring.insert_node(1, 1);
+        ring.insert_node(2, 1);
+
+        let points: Vec<u32> = (0..1000).collect();
+        let nodes = ring.get_nodes_batch(&points);
+        for (point, node) in points.iter().zip(nodes) {
+            assert_eq!(node, ring.get_node(point));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_ring_get_nodes_batch_empty_ring() {
+        let ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.get_nodes_batch(&[0]);
+    }
+
+    #[test]
+    fn test_freeze() {
+        let new_ring = || {
+            let mut ring = Ring::with_hasher(BuildDefaultHasher::default());
+            for node in 0..10 {
+                ring.insert_node(node, node as usize + 1);
+            }
+            ring.remove_node(&3);
+            ring
+        };
+        let ring = new_ring();
+        let frozen = new_ring().freeze();
+
+        assert_eq!(frozen.len(), 9);
+        let mut nodes: Vec<_> = frozen.iter().collect();
+        nodes.sort_unstable();
+        let mut expected: Vec<_> = ring.iter().collect();
+        expected.sort_unstable();
+        assert_eq!(nodes, expected);
+
+        let points: Vec<u32> = (0..1000).collect();
+        for point in &points {
+            assert_eq!(frozen.get_node(point), ring.get_node(point));
+            assert_eq!(frozen.get_nodes(point, 4), ring.get_nodes(point, 4));
+            assert_eq!(frozen.get_nodes(point, 20), ring.get_nodes(point, 20));
+        }
+        assert_eq!(
+            frozen.get_nodes_batch(&points),
+            ring.get_nodes_batch(&points)
+        );
+    }
+
+    #[test]
+    fn test_freeze_wraps_around() {
+        let mut ring = Ring::with_hasher(BuildAddHasher::default());
+        ring.insert_node(1, 1);
+        ring.insert_node(2, 1);
+        let ring = ring.freeze();
+        let mut tokens = ring.tokens[1..].to_vec();
+        tokens.sort_unstable();
+
+        assert_eq!(ring.get_node_by_hash(0), &1);
+        assert_eq!(ring.get_node_by_hash(tokens[0]), &1);
+        assert_eq!(ring.get_node_by_hash(tokens[0] + 1), &2);
+        assert_eq!(ring.get_node_by_hash(tokens[1]), &2);
+        assert_eq!(ring.get_node_by_hash(tokens[1] + 1), &1);
+        assert_eq!(ring.get_nodes_by_hash(tokens[1] + 1, 2), [&1, &2]);
+        assert_eq!(ring.get_nodes_batch(&[u64::MAX - 1, 0]), [&1, &1]);
+    }
+
+    #[test]
+    fn test_freeze_get_nodes_zero_replicas() {
+        let mut ring = Ring::new();
+        ring.insert_node("a", 1);
+        ring.insert_node("b", 0);
+        assert_eq!(ring.get_nodes(&"p", 2), [&"a"]);
+
+        let ring = ring.freeze();
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring.get_nodes(&"p", 2), [&"a"]);
+    }
+
+    #[test]
+    fn test_eytzinger() {
+        for len in 0..64 {
+            let mut indices = Vec::new();
+            let mut index = eytzinger_first(len);
+            while index != 0 {
+                indices.push(index);
+                index = eytzinger_next(index, len);
+            }
+            indices.sort_unstable();
+            assert_eq!(indices, (1..=len).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_freeze_empty() {
+        let ring: FrozenRing<u32> = Ring::new().freeze();
+        assert!(ring.is_empty());
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+        assert_eq!(ring.get_nodes(&0, 2), Vec::<&u32>::new());
+        assert_eq!(ring.get_nodes_batch::<u32>(&[]), Vec::<&u32>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_freeze_get_node_empty_ring() {
+        let ring: FrozenRing<u32> = Ring::new().freeze();
+        ring.get_node(&0);
+    }
+
+    #[test]
+    fn test_thaw() {
+        let new_ring = || {
+            let mut ring = Ring::with_hasher(BuildDefaultHasher::default());
+            ring.insert_node(0, 2);
+            ring.insert_node(1, 3);
+            ring
+        };
+        let mut ring = new_ring();
+        let mut thawed = new_ring().freeze().thaw();
+
+        for point in 0..1000 {
+            assert_eq!(thawed.get_node(&point), ring.get_node(&point));
+        }
+
+        thawed.insert_node(2, 1);
+        ring.insert_node(2, 1);
+        thawed.remove_node(&0);
+        ring.remove_node(&0);
+        assert_eq!(thawed.len(), 2);
+        for point in 0..1000 {
+            assert_eq!(thawed.get_node(&point), ring.get_node(&point));
+        }
+    }
+
+    #[test]
+    fn test_new_stable_hasher() {
+        let mut ring = Ring::new();
+        ring.insert_node(0, 10);
+        ring.insert_node(1, 10);
+        ring.insert_node(2, 10);
+        let nodes: Vec<u32> = (0..10).map(|point| *ring.get_node(&point)).collect();
+        assert_eq!(nodes, [2, 2, 2, 1, 1, 1, 0, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_migrations() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        for point in 0..1000 {
+            client.insert_point(point);
+        }
+        let get_nodes = |client: &Client<u32, u32, BuildDefaultHasher>| {
+            (0..1000)
+                .map(|point| (point, *client.get_node(&point)))
+                .collect::<Vec<(u32, u32)>>()
+        };
+
+        let before = get_nodes(&client);
+        let migrations = client.insert_node(2, 10);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+
+        let before = get_nodes(&client);
+        let migrations = client.remove_node(&0);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+    }
+
     #[test]
     fn test_insert_point() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(0);
         assert_eq!(client.get_points(&0), [&0u32]);
     }
 
     #[test]
     fn test_remove_point() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(0);
         client.remove_point(&0);
         let expected: [&u32; 0] = [];
         assert_eq!(client.get_points(&0), expected);
@@ -828,16 +3075,347 @@ mod tests {
 
     #[test]
     fn test_iter() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&1);
-        client.insert_point(&2);
-        client.insert_point(&3);
-        client.insert_point(&4);
-        client.insert_point(&5);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(1);
+        client.insert_point(2);
+        client.insert_point(3);
+        client.insert_point(4);
+        client.insert_point(5);
         let mut actual: Vec<(&u32, Vec<&u32>)> = client.iter().collect();
         actual[0].1.sort();
         assert_eq!(actual[0].0, &0);
         assert_eq!(actual[0].1, [&1, &2, &3, &4, &5]);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_node(1, 2);
+        client.insert_node(2, 1);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+
+        let json = serde_json::to_string(&client).unwrap();
+        let other: Client<u32, u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.len(), 3);
+        for node in 0..3 {
+            let mut expected = client.get_points(&node);
+            let mut actual = other.get_points(&node);
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual);
+        }
+        for point in 0..200 {
+            assert_eq!(client.get_node(&point), other.get_node(&point));
+            assert_eq!(client.ring.get_node(&point), other.ring.get_node(&point));
+        }
+
+        let json = serde_json::to_string(&client.ring).unwrap();
+        let ring: Ring<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+        for point in 0..200 {
+            assert_eq!(client.get_nodes(&point, 3), ring.get_nodes(&point, 3));
+        }
+
+        let frozen = ring.freeze();
+        let json = serde_json::to_string(&frozen).unwrap();
+        let other: FrozenRing<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+        for point in 0..200 {
+            assert_eq!(frozen.get_nodes(&point, 3), other.get_nodes(&point, 3));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_client() {
+        let json = r#"{"nodes":[[0,0]],"points":[]}"#;
+        assert!(serde_json::from_str::<Client<u32, u32, BuildDefaultHasher>>(json).is_err());
+
+        let json = r#"{"nodes":[],"points":[0]}"#;
+        assert!(serde_json::from_str::<Client<u32, u32, BuildDefaultHasher>>(json).is_err());
+    }
+
+    fn assert_bounded<H>(client: &BoundedClient<u32, u32, H>, points: usize) {
+        let capacity = client.capacity();
+        let mut total = 0;
+        for (_, node_points) in client.iter() {
+            assert!(node_points.len() <= capacity);
+            total += node_points.len();
+        }
+        assert_eq!(total, points);
+    }
+
+    #[test]
+    fn test_bounded_try_new() {
+        assert!(BoundedClient::<u32, u32>::try_new(0f64).is_ok());
+        assert_eq!(
+            BoundedClient::<u32, u32>::try_new(-1f64).err(),
+            Some(Error::InvalidCapacity),
+        );
+        assert_eq!(
+            BoundedClient::<u32, u32>::try_new(f64::NAN).err(),
+            Some(Error::InvalidCapacity),
+        );
+        assert_eq!(
+            BoundedClient::<u32, u32>::try_new(f64::INFINITY).err(),
+            Some(Error::InvalidCapacity),
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bounded_panic_new_negative_epsilon() {
+        let _client: BoundedClient<u32, u32> = BoundedClient::new(-1f64);
+    }
+
+    #[test]
+    fn test_bounded_try_empty_client() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.25);
+        assert_eq!(client.try_get_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_insert_point(0), Err(Error::EmptyRing));
+        assert_eq!(client.try_remove_point(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_remove_node(&0), Err(Error::UnknownNode));
+        assert_eq!(client.try_get_points(&0), Err(Error::UnknownNode));
+        assert_eq!(client.try_insert_node(0, 0), Err(Error::InvalidWeight));
+        assert!(client.get_nodes(&0, 1).is_empty());
+
+        client.insert_node(0, 1);
+        client.insert_point(0);
+        assert_eq!(client.try_remove_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.get_points(&0), [&0]);
+    }
+
+    #[test]
+    fn test_bounded_zero_epsilon() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0f64);
+        client.insert_node(0, 1);
+        client.insert_node(1, 1);
+        for point in 0..10 {
+            client.insert_point(point);
+        }
+        assert_eq!(client.capacity(), 5);
+        assert_eq!(client.get_points(&0).len(), 5);
+        assert_eq!(client.get_points(&1).len(), 5);
+    }
+
+    #[test]
+    fn test_bounded_insert_and_remove() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.1);
+        for node in 0..5 {
+            client.insert_node(node, 10);
+        }
+        for point in 0..1000 {
+            client.insert_point(point);
+        }
+        assert_eq!(client.capacity(), 220);
+        assert_bounded(&client, 1000);
+
+        for point in 0..500 {
+            client.remove_point(&point);
+        }
+        assert_eq!(client.capacity(), 110);
+        assert_bounded(&client, 500);
+
+        client.insert_node(5, 10);
+        assert_eq!(client.capacity(), 92);
+        assert_bounded(&client, 500);
+
+        client.remove_node(&0);
+        client.remove_node(&1);
+        assert_eq!(client.capacity(), 138);
+        assert_bounded(&client, 500);
+
+        for point in 500..1000 {
+            assert!(client.get_node(&point) != &0);
+            assert!(client.get_node(&point) != &1);
+            assert!(client.get_points(client.get_node(&point)).contains(&&point));
+        }
+    }
+
+    #[test]
+    fn test_bounded_insert_node_moves_closer_points() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 1f64);
+        client.insert_node(0, 10);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 10);
+        ring.insert_node(1, 10);
+
+        // With a capacity of 100 points, the bound is not reached and points are associated with
+        // the same nodes as in an unbounded ring.
+        client.insert_node(1, 10);
+        assert_eq!(client.capacity(), 100);
+        for point in 0..100 {
+            assert_eq!(client.get_node(&point), ring.get_node(&point));
+        }
+    }
+
+    #[test]
+    fn test_bounded_reinsert_node() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.25);
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+        client.insert_node(1, 20);
+        assert_eq!(client.len(), 2);
+        assert_bounded(&client, 100);
+    }
+
+    #[test]
+    fn test_bounded_get_nodes() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.25);
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        client.insert_node(2, 10);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+        for point in 0..200 {
+            let nodes = client.get_nodes(&point, 3);
+            assert_eq!(nodes.len(), 3);
+            assert_eq!(nodes[0], client.get_node(&point));
+            assert!(nodes[1] != nodes[0] && nodes[2] != nodes[0] && nodes[1] != nodes[2]);
+        }
+    }
+
+    #[test]
+    fn test_bounded_migrations() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.1);
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        for point in 0..1000 {
+            client.insert_point(point);
+        }
+        let get_nodes = |client: &BoundedClient<u32, u32, BuildDefaultHasher>| {
+            (0..1000)
+                .map(|point| (point, *client.get_node(&point)))
+                .collect::<Vec<(u32, u32)>>()
+        };
+
+        let before = get_nodes(&client);
+        let migrations = client.insert_node(2, 10);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+
+        let before = get_nodes(&client);
+        let migrations = client.insert_node(2, 20);
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+
+        let before = get_nodes(&client);
+        let migrations = client.remove_node(&0);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+    }
+
+    #[test]
+    fn test_bounded_remove_point_migrations() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.0);
+        for node in 0..3 {
+            client.insert_node(node, 10);
+        }
+        for point in 0..300 {
+            client.insert_point(point);
+        }
+        let get_nodes = |client: &BoundedClient<u32, u32, BuildDefaultHasher>| {
+            (0..300)
+                .filter(|point| client.points.contains_key(point))
+                .map(|point| (point, *client.get_node(&point)))
+                .collect::<Vec<(u32, u32)>>()
+        };
+
+        let mut removed_points: Vec<u32> = client.get_points(&0).into_iter().cloned().collect();
+        removed_points.sort_unstable();
+        let mut moved = false;
+        for point in removed_points.into_iter().take(50) {
+            let mut before = get_nodes(&client);
+            before.retain(|entry| entry.0 != point);
+            let migrations = client.remove_point(&point);
+            moved |= !migrations.is_empty();
+            test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+        }
+        assert!(moved);
+        assert_eq!(client.capacity(), 84);
+    }
+
+    #[test]
+    fn test_bounded_deterministic() {
+        let run = || {
+            let mut client: BoundedClient<u32, u32> = BoundedClient::new(0.0);
+            for node in 0..4 {
+                client.insert_node(node, 5);
+            }
+            for point in 0..400 {
+                client.insert_point(point);
+            }
+            for point in 0..100 {
+                client.remove_point(&point);
+            }
+            client.insert_node(4, 5);
+            client.remove_node(&1);
+            (100..400)
+                .map(|point| *client.get_node(&point))
+                .collect::<Vec<u32>>()
+        };
+
+        let expected = run();
+        for _ in 0..10 {
+            assert_eq!(run(), expected);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_bounded_serde() {
+        let mut client: BoundedClient<u32, u32, BuildDefaultHasher> =
+            BoundedClient::with_hasher(BuildDefaultHasher::default(), 0.25);
+        client.insert_node(0, 3);
+        client.insert_node(1, 2);
+        client.insert_node(2, 1);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+
+        let json = serde_json::to_string(&client).unwrap();
+        let other: BoundedClient<u32, u32, BuildDefaultHasher> =
+            serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.len(), 3);
+        assert_eq!(other.capacity(), client.capacity());
+        for point in 0..200 {
+            assert_eq!(client.get_node(&point), other.get_node(&point));
+        }
+
+        let json = r#"{"epsilon":-1.0,"nodes":[[0,1]],"points":[]}"#;
+        assert!(serde_json::from_str::<BoundedClient<u32, u32>>(json).is_err());
+
+        let json = r#"{"epsilon":0.25,"nodes":[[0,1]],"points":[[0,1]]}"#;
+        assert!(serde_json::from_str::<BoundedClient<u32, u32>>(json).is_err());
+    }
+
+    #[test]
+    fn test_owned_nodes_and_points() {
+        let mut client: Client<String, String, BuildDefaultHasher> = Client::default();
+        client.insert_node(String::from("node-1"), 3);
+        client.insert_point(String::from("point-1"));
+        client.insert_node(String::from("node-2"), 3);
+        client.remove_node(&String::from("node-1"));
+        assert_eq!(client.get_node(&String::from("point-1")), "node-2");
+        assert_eq!(client.get_points(&String::from("node-2")), ["point-1"]);
+    }
 }
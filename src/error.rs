@@ -0,0 +1,56 @@
+//! Error type returned by the fallible methods of the rings and clients.
+
+use std::error;
+use std::fmt;
+
+/// An error returned by the `try_*` methods of the rings and clients.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::consistent::Ring;
+/// use hash_rings::Error;
+///
+/// let ring: Ring<&str> = Ring::new();
+///
+/// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    /// The ring has no nodes, or the operation would leave the ring with no nodes.
+    EmptyRing,
+    /// The node does not exist in the ring.
+    UnknownNode,
+    /// The weight or number of replicas of a node is zero, negative, or not finite.
+    InvalidWeight,
+    /// The capacity of the ring, such as the size of a lookup table or the number of probes, is
+    /// too small.
+    InvalidCapacity,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Error::EmptyRing => "empty ring",
+            Error::UnknownNode => "unknown node",
+            Error::InvalidWeight => "invalid weight",
+            Error::InvalidCapacity => "invalid capacity",
+        };
+        f.write_str(description)
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Error::EmptyRing.to_string(), "empty ring");
+        assert_eq!(Error::UnknownNode.to_string(), "unknown node");
+        assert_eq!(Error::InvalidWeight.to_string(), "invalid weight");
+        assert_eq!(Error::InvalidCapacity.to_string(), "invalid capacity");
+    }
+}
@@ -0,0 +1,101 @@
+//! Default hash builder used by the rings and clients.
+
+use siphasher::sip::SipHasher;
+use std::hash::BuildHasher;
+
+/// A hash builder that produces the same hashes across processes, machines, and restarts.
+///
+/// `StableBuildHasher` builds SipHash-2-4 hashers with fixed keys, so rings constructed with the
+/// same nodes map points to the same nodes regardless of where they are constructed. It is the
+/// default hash builder of all rings and clients. Unlike `RandomState`, it offers no protection
+/// against HashDoS attacks when points are chosen by an adversary; use `with_keys` with secret
+/// keys shared by all processes in that case.
+///
+/// The hashes produced by `StableBuildHasher` are part of the public API and will only change in a
+/// new major version. Since `Hash` implementations of integers such as `usize` write their native
+/// representation, placements only agree across machines with the same pointer width and
+/// endianness.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::consistent::Ring;
+/// use hash_rings::StableBuildHasher;
+///
+/// let mut ring: Ring<&str> = Ring::new();
+/// let mut other = Ring::with_hasher(StableBuildHasher::new());
+///
+/// ring.insert_node("node-1", 1);
+/// ring.insert_node("node-2", 1);
+/// other.insert_node("node-1", 1);
+/// other.insert_node("node-2", 1);
+///
+/// assert_eq!(ring.get_node(&"point-1"), other.get_node(&"point-1"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StableBuildHasher {
+    k0: u64,
+    k1: u64,
+}
+
+impl StableBuildHasher {
+    /// Constructs a new `StableBuildHasher` with the default fixed keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let hash_builder = StableBuildHasher::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new `StableBuildHasher` with the specified keys.
+    ///
+    /// Rings constructed with the same keys produce the same placements. Note that deserializing a
+    /// ring rebuilds it with the default keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let hash_builder = StableBuildHasher::with_keys(1, 2);
+    /// ```
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self { k0, k1 }
+    }
+}
+
+impl BuildHasher for StableBuildHasher {
+    type Hasher = SipHasher;
+
+    fn build_hasher(&self) -> SipHasher {
+        SipHasher::new_with_keys(self.k0, self.k1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableBuildHasher;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_stable_hashes() {
+        let hash_builder = StableBuildHasher::new();
+        assert_eq!(hash_builder.hash_one(0u64), 16_738_165_381_834_614_119);
+        assert_eq!(hash_builder.hash_one("node-1"), 13_048_675_127_081_726_259);
+    }
+
+    #[test]
+    fn test_with_keys() {
+        let hash_builder = StableBuildHasher::with_keys(1, 2);
+        assert_ne!(
+            hash_builder.hash_one(0u64),
+            StableBuildHasher::new().hash_one(0u64)
+        );
+        assert_eq!(hash_builder.hash_one(0u64), 15_738_495_304_338_208_688);
+    }
+}
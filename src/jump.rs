@@ -1,9 +1,88 @@
 //! Hashing ring implemented using jump hashing.
 
 use crate::util;
-use std::collections::hash_map::RandomState;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
 use std::hash::{BuildHasher, Hash};
 
+// Maps `key` to a bucket in the range [0, num_buckets) using `Mode::Standard`.
+pub(crate) fn jump(mut key: u64, num_buckets: u64) -> u64 {
+    let mut i: i64 = -1;
+    let mut j: i64 = 0;
+
+    while j < num_buckets as i64 {
+        i = j;
+        key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+        j = (((i.wrapping_add(1)) as f64) * ((1i64 << 31) as f64)
+            / (((key >> 33).wrapping_add(1)) as f64)) as i64;
+    }
+    i as u64
+}
+
+/// The implementation of jump hashing used to map a hash to a node.
+///
+/// The implementations follow the same algorithm, but they compute the jumps with different
+/// floating point arithmetic and random number generators, so they disagree on some hashes when
+/// the number of nodes is large. `Mode::Standard` and `Mode::Reference` support up to `i64::MAX`
+/// nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Mode {
+    /// The implementation used by this crate before other modes were added.
+    #[default]
+    Standard,
+    /// The reference C++ implementation from "A Fast, Minimal Memory, Consistent Hash Algorithm"
+    /// by Lamping and Veach.
+    Reference,
+    /// The implementation of `Hashing.consistentHash` in Guava, which only supports up to
+    /// `i32::MAX` nodes.
+    Guava,
+}
+
+impl Mode {
+    fn get_bucket(self, hash: u64, nodes: u64) -> u64 {
+        match self {
+            Mode::Standard => jump(hash, nodes),
+            Mode::Reference => Self::jump_reference(hash, nodes),
+            Mode::Guava => Self::jump_guava(hash, nodes),
+        }
+    }
+
+    fn jump_reference(mut key: u64, nodes: u64) -> u64 {
+        let mut b: i64 = -1;
+        let mut j: i64 = 0;
+
+        while j < nodes as i64 {
+            b = j;
+            key = key.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
+            j = ((b + 1) as f64 * ((1i64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+        }
+        b as u64
+    }
+
+    // Java's conversions from `double` to `int` saturate like `as` does, and the increment of the
+    // 31-bit random value wraps around to `i32::MIN` like it does in Java.
+    fn jump_guava(mut state: u64, nodes: u64) -> u64 {
+        let mut candidate: i32 = 0;
+
+        loop {
+            state = state
+                .wrapping_mul(2_862_933_555_777_941_757)
+                .wrapping_add(1);
+            let next_double =
+                f64::from(((state >> 33) as i32).wrapping_add(1)) / (1u64 << 31) as f64;
+            let next = (f64::from(candidate + 1) / next_double) as i32;
+            if next >= 0 && (next as u64) < nodes {
+                candidate = next;
+            } else {
+                return candidate as u64;
+            }
+        }
+    }
+}
+
 /// A hashing ring implemented using jump hashing.
 ///
 /// Jump hashing is based on using a hash of the key as the seed for a random number generator and
@@ -12,7 +91,8 @@ use std::hash::{BuildHasher, Hash};
 ///
 /// Jump hashing is very fast and executes in `O(ln n)` time. It also has no memory overhead and has
 /// virtually perfect key distribution. However, the main limitation of jump hashing is that it
-/// returns an integer in the range [0, nodes) and it does not support arbitrary node names.
+/// returns an integer in the range [0, nodes) and it does not support arbitrary node names. Use
+/// `NamedRing` to map the integers to node ids.
 ///
 /// # Examples
 ///
@@ -28,17 +108,19 @@ use std::hash::{BuildHasher, Hash};
 /// assert_eq!(ring.get_node(&"foo"), 8);
 /// assert_eq!(ring.nodes(), 100);
 /// ```
-pub struct Ring<H = RandomState> {
-    nodes: u32,
+#[derive(Clone)]
+pub struct Ring<H = StableBuildHasher> {
+    nodes: u64,
+    mode: Mode,
     hash_builder: H,
 }
 
-impl Ring<RandomState> {
+impl Ring<StableBuildHasher> {
     /// Constructs a new `Ring` with a specified number of nodes.
     ///
     /// # Panics
     ///
-    /// Panics if the number of nodes is zero.
+    /// Panics if the number of nodes is zero or greater than `i64::MAX`.
     ///
     /// # Examples
     ///
@@ -47,9 +129,28 @@ impl Ring<RandomState> {
     ///
     /// let ring: Ring = Ring::new(100);
     /// ```
-    pub fn new(nodes: u32) -> Self {
+    pub fn new(nodes: u64) -> Self {
         Self::with_hasher(Default::default(), nodes)
     }
+
+    /// Constructs a new `Ring` with a specified number of nodes.
+    ///
+    /// Returns `Error::EmptyRing` if the number of nodes is zero and `Error::InvalidCapacity` if it
+    /// is greater than `i64::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(Ring::try_new(100).is_ok());
+    /// assert_eq!(Ring::try_new(0).err(), Some(Error::EmptyRing));
+    /// assert_eq!(Ring::try_new(u64::MAX).err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_new(nodes: u64) -> Result<Self, Error> {
+        Self::try_with_hasher(Default::default(), nodes)
+    }
 }
 
 impl<H> Ring<H> {
@@ -57,7 +158,7 @@ impl<H> Ring<H> {
     ///
     /// # Panics
     ///
-    /// Panics if the number of nodes is zero.
+    /// Panics if the number of nodes is zero or greater than `i64::MAX`.
     ///
     /// # Examples
     ///
@@ -70,12 +171,90 @@ impl<H> Ring<H> {
     ///
     /// let ring: Ring<_> = Ring::with_hasher(DefaultBuildHasher::default(), 100);
     /// ```
-    pub fn with_hasher(hash_builder: H, nodes: u32) -> Self {
-        assert!(nodes >= 1);
-        Self {
+    pub fn with_hasher(hash_builder: H, nodes: u64) -> Self {
+        match Self::try_with_hasher(hash_builder, nodes) {
+            Ok(ring) => ring,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new `Ring` with a specified number of nodes and hash builder.
+    ///
+    /// Returns `Error::EmptyRing` if the number of nodes is zero and `Error::InvalidCapacity` if it
+    /// is greater than `i64::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::Ring;
+    /// use hash_rings::Error;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring = Ring::try_with_hasher(DefaultBuildHasher::default(), 0);
+    /// assert_eq!(ring.err(), Some(Error::EmptyRing));
+    /// ```
+    pub fn try_with_hasher(hash_builder: H, nodes: u64) -> Result<Self, Error> {
+        Self::try_with_mode(hash_builder, nodes, Mode::Standard)
+    }
+
+    /// Constructs a new `Ring` with a specified number of nodes, hash builder, and implementation
+    /// of jump hashing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of nodes is zero, greater than `i64::MAX`, or greater than `i32::MAX`
+    /// and `mode` is `Mode::Guava`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::{Mode, Ring};
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let ring = Ring::with_mode(StableBuildHasher::new(), 100, Mode::Guava);
+    /// ```
+    pub fn with_mode(hash_builder: H, nodes: u64, mode: Mode) -> Self {
+        match Self::try_with_mode(hash_builder, nodes, mode) {
+            Ok(ring) => ring,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new `Ring` with a specified number of nodes, hash builder, and implementation
+    /// of jump hashing.
+    ///
+    /// Returns `Error::EmptyRing` if the number of nodes is zero and `Error::InvalidCapacity` if
+    /// the number of nodes is greater than `i64::MAX`, or greater than `i32::MAX` and `mode` is
+    /// `Mode::Guava`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::{Mode, Ring};
+    /// use hash_rings::{Error, StableBuildHasher};
+    ///
+    /// let ring = Ring::try_with_mode(StableBuildHasher::new(), 1 << 31, Mode::Guava);
+    /// assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_mode(hash_builder: H, nodes: u64, mode: Mode) -> Result<Self, Error> {
+        if nodes == 0 {
+            return Err(Error::EmptyRing);
+        }
+        let max_nodes = match mode {
+            Mode::Guava => i32::MAX as u64,
+            Mode::Standard | Mode::Reference => i64::MAX as u64,
+        };
+        if nodes > max_nodes {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self {
             hash_builder,
             nodes,
-        }
+            mode,
+        })
     }
 
     /// Returns the node associated with a key.
@@ -92,22 +271,107 @@ impl<H> Ring<H> {
     /// let ring = Ring::with_hasher(DefaultBuildHasher::default(), 100);
     /// assert_eq!(ring.get_node(&"foo"), 8);
     /// ```
-    pub fn get_node<T>(&self, key: &T) -> u32
+    pub fn get_node<T>(&self, key: &T) -> u64
     where
         T: Hash,
         H: BuildHasher,
     {
-        let mut h = util::gen_hash(&self.hash_builder, key);
-        let mut i: i64 = -1;
-        let mut j: i64 = 0;
+        self.get_node_by_hash(util::gen_hash(&self.hash_builder, key))
+    }
+
+    /// Returns the node associated with a hash of a key. The hash of a key is the value returned
+    /// by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same node as
+    /// `get_node` for the key.
+    ///
+    /// Since the node only depends on the hash and the number of nodes, a ring with `Mode::Guava`
+    /// returns the same node as `Hashing.consistentHash` in Guava for the same 64-bit hash, such
+    /// as the value of `HashCode.padToLong`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::{Mode, Ring};
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let ring = Ring::with_mode(StableBuildHasher::new(), 100, Mode::Guava);
+    /// assert_eq!(ring.get_node_by_hash(1), 55);
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> u64 {
+        self.mode.get_bucket(hash, self.nodes)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a key in order of preference.
+    ///
+    /// The first node is the node returned by `get_node`. The `k`-th following node is found by
+    /// jump hashing a rehash of the key over the `nodes - k` nodes that have not been returned
+    /// yet. Fewer than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::Ring;
+    ///
+    /// let ring = Ring::new(100);
+    ///
+    /// let nodes = ring.get_nodes(&"foo", 3);
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(nodes[0], ring.get_node(&"foo"));
+    /// ```
+    pub fn get_nodes<T>(&self, key: &T, n: usize) -> Vec<u64>
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, key), n)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a key in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(100);
+    /// let hash = StableBuildHasher::new().hash_one(&"foo");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 3), ring.get_nodes(&"foo", 3));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<u64>
+    where
+        H: BuildHasher,
+    {
+        Self::get_buckets(&self.hash_builder, self.mode, hash, self.nodes, n)
+    }
 
-        while j < i64::from(self.nodes) {
-            i = j;
-            h = h.wrapping_mul(2_862_933_555_777_941_757).wrapping_add(1);
-            j = (((i.wrapping_add(1)) as f64) * ((1i64 << 31) as f64)
-                / (((h >> 33).wrapping_add(1)) as f64)) as i64;
+    fn get_buckets(hash_builder: &H, mode: Mode, hash: u64, nodes: u64, n: usize) -> Vec<u64>
+    where
+        H: BuildHasher,
+    {
+        let n = cmp::min(n as u64, nodes);
+        let mut ret = Vec::with_capacity(n as usize);
+        let mut sorted: Vec<u64> = Vec::with_capacity(n as usize);
+        for k in 0..n {
+            let rehash = if k == 0 {
+                hash
+            } else {
+                util::combine_hash(hash_builder, hash, k)
+            };
+            // Map the index among the remaining nodes to the index among all nodes.
+            let mut node = mode.get_bucket(rehash, nodes - k);
+            for &taken in &sorted {
+                if taken <= node {
+                    node += 1;
+                }
+            }
+            let position = sorted.binary_search(&node).unwrap_err();
+            sorted.insert(position, node);
+            ret.push(node);
         }
-        i as u32
+        ret
     }
 
     /// Returns the number of nodes in the ring.
@@ -120,15 +384,426 @@ impl<H> Ring<H> {
     /// let ring = Ring::new(100);
     /// assert_eq!(ring.nodes(), 100);
     /// ```
-    pub fn nodes(&self) -> u32 {
+    pub fn nodes(&self) -> u64 {
         self.nodes
     }
+
+    /// Returns the implementation of jump hashing used by the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::{Mode, Ring};
+    ///
+    /// let ring = Ring::new(100);
+    /// assert_eq!(ring.mode(), Mode::Standard);
+    /// ```
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+/// Serializes the number of nodes and the mode of the ring. The hash builder is not serialized.
+#[cfg(feature = "serde")]
+impl<H> Serialize for Ring<H> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State {
+            nodes: u64,
+            mode: Mode,
+        }
+
+        State {
+            nodes: self.nodes,
+            mode: self.mode,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from its number of nodes and mode. The ring is rebuilt with `H::default()`,
+/// so lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, H> Deserialize<'de> for Ring<H>
+where
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State {
+            nodes: u64,
+            #[serde(default)]
+            mode: Mode,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Self::try_with_mode(H::default(), state.nodes, state.mode).map_err(de::Error::custom)
+    }
+}
+
+/// A hashing ring implemented using jump hashing that maps buckets to node ids.
+///
+/// The `i`-th node is associated with the `i`-th bucket of jump hashing. Since jump hashing only
+/// moves keys to a new last bucket when a bucket is added, nodes can only be appended or removed
+/// from the end. Appending a node only moves keys to the new node, and removing the last node only
+/// moves the keys of the removed node.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::jump::NamedRing;
+///
+/// let mut ring = NamedRing::new(vec!["node-1", "node-2"]);
+/// ring.push_node("node-3");
+///
+/// assert_eq!(ring.get_node(&"point-1"), &"node-3");
+/// assert_eq!(ring.pop_node(), Some("node-3"));
+/// assert_eq!(ring.get_node(&"point-1"), &"node-1");
+/// ```
+#[derive(Clone)]
+pub struct NamedRing<T, H = StableBuildHasher> {
+    nodes: Vec<T>,
+    hash_builder: H,
+}
+
+impl<T> NamedRing<T, StableBuildHasher> {
+    /// Constructs a new `NamedRing<T>` with a specified list of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2"]);
+    /// ```
+    pub fn new(nodes: Vec<T>) -> Self {
+        Self::with_hasher(Default::default(), nodes)
+    }
+}
+
+impl<T, H> NamedRing<T, H> {
+    /// Constructs a new `NamedRing<T, H>` with a specified hash builder and list of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring = NamedRing::with_hasher(DefaultBuildHasher::default(), vec!["node-1", "node-2"]);
+    /// ```
+    pub fn with_hasher(hash_builder: H, nodes: Vec<T>) -> Self {
+        Self {
+            nodes,
+            hash_builder,
+        }
+    }
+
+    /// Appends a node to the ring. Only keys that are moved to the new node change nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let mut ring = NamedRing::new(vec!["node-1"]);
+    /// ring.push_node("node-2");
+    /// assert_eq!(ring.len(), 2);
+    /// ```
+    pub fn push_node(&mut self, id: T) {
+        self.nodes.push(id);
+    }
+
+    /// Removes the last node from the ring and returns it, or `None` if the ring is empty. Only
+    /// keys of the removed node change nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let mut ring = NamedRing::new(vec!["node-1"]);
+    /// assert_eq!(ring.pop_node(), Some("node-1"));
+    /// assert_eq!(ring.pop_node(), None);
+    /// ```
+    pub fn pop_node(&mut self) -> Option<T> {
+        self.nodes.pop()
+    }
+
+    /// Returns the node associated with a key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1"]);
+    /// assert_eq!(ring.get_node(&"point-1"), &"node-1");
+    /// ```
+    pub fn get_node<U>(&self, key: &U) -> &T
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        match self.try_get_node(key) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a key.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = NamedRing::new(vec![]);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// ring.push_node("node-1");
+    /// assert_eq!(ring.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node<U>(&self, key: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, key))
+    }
+
+    /// Returns the node associated with a hash of a key. The hash of a key is the value returned
+    /// by `hash_one` on the ring's hash builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2"]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a key.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = NamedRing::new(vec![]);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.push_node("node-1");
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        if self.nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        let bucket = jump(hash, self.nodes.len() as u64);
+        Ok(&self.nodes[bucket as usize])
+    }
+
+    /// Returns up to `n` distinct nodes associated with a key in order of preference.
+    ///
+    /// The nodes are the nodes of the buckets returned by `Ring::get_nodes`, so the first node is
+    /// the node returned by `get_node`. Fewer than `n` nodes are returned if the ring has fewer
+    /// than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2", "node-3"]);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 4);
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, key: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, key), n)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a key in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2", "node-3"]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T>
+    where
+        H: BuildHasher,
+    {
+        Ring::get_buckets(
+            &self.hash_builder,
+            Mode::Standard,
+            hash,
+            self.nodes.len() as u64,
+            n,
+        )
+        .into_iter()
+        .map(|bucket| &self.nodes[bucket as usize])
+        .collect()
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2"]);
+    /// assert_eq!(ring.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let mut ring = NamedRing::new(vec!["node-1"]);
+    ///
+    /// assert!(!ring.is_empty());
+    /// ring.pop_node();
+    /// assert!(ring.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield the nodes in order of their
+    /// buckets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::jump::NamedRing;
+    ///
+    /// let ring = NamedRing::new(vec!["node-1", "node-2"]);
+    ///
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some(&"node-1"));
+    /// assert_eq!(iterator.next(), Some(&"node-2"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter()
+    }
+}
+
+impl<'a, T, H> IntoIterator for &'a NamedRing<T, H> {
+    type IntoIter = Box<dyn Iterator<Item = &'a T> + 'a>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Serializes the ids of the nodes in the ring in order of their buckets. The hash builder is not
+/// serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for NamedRing<T, H>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: &'a [T],
+        }
+
+        State { nodes: &self.nodes }.serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids of its nodes in order of their buckets. The ring is rebuilt
+/// with `H::default()`, so lookups are only preserved if the default hash builder is
+/// deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for NamedRing<T, H>
+where
+    T: Deserialize<'de>,
+    H: Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            nodes: Vec<T>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Ok(Self::with_hasher(H::default(), state.nodes))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Ring;
+    use super::{Mode, NamedRing, Ring};
     use crate::test_util::BuildDefaultHasher;
+    use crate::Error;
+    use std::cmp;
+    use std::hash::BuildHasher;
 
     #[test]
     #[should_panic]
@@ -136,15 +811,301 @@ mod tests {
         let _ring = Ring::with_hasher(BuildDefaultHasher::default(), 0);
     }
 
+    #[test]
+    fn test_try_new_zero_nodes() {
+        let ring = Ring::try_with_hasher(BuildDefaultHasher::default(), 0);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+    }
+
     #[test]
     fn test_get_node() {
         let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
         assert_eq!(ring.get_node(&"foo"), 8);
     }
 
+    #[test]
+    fn test_get_nodes() {
+        let ring = Ring::with_hasher(BuildDefaultHasher::default(), 10);
+        let mut nodes = ring.get_nodes(&"foo", 20);
+        assert_eq!(nodes[0], ring.get_node(&"foo"));
+        assert_eq!(ring.get_nodes(&"foo", 3)[..], nodes[..3]);
+
+        nodes.sort();
+        assert_eq!(nodes, (0..10).collect::<Vec<u64>>());
+    }
+
     #[test]
     fn test_nodes() {
         let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
         assert_eq!(ring.nodes(), 100);
     }
+
+    fn mode_ring(nodes: u64, mode: Mode) -> Ring<BuildDefaultHasher> {
+        Ring::with_mode(BuildDefaultHasher::default(), nodes, mode)
+    }
+
+    // Golden values of `Hashing.consistentHash` from Guava's `HashingTest`. The reference C++ from
+    // the paper agrees with them, as does the standard mode.
+    #[test]
+    fn test_get_node_by_hash_golden() {
+        let expected = [
+            0, 55, 62, 8, 45, 59, 86, 97, 82, 59, 73, 37, 17, 56, 86, 21, 90, 37, 38, 83,
+        ];
+        let cases = [
+            (10_863_919_174_838_991, 11, 6),
+            (2_016_238_256_797_177_309, 11, 3),
+            (1_673_758_223_894_951_030, 11, 5),
+            (2, 100_001, 80343),
+            (2201, 100_001, 22152),
+            (2202, 100_001, 15018),
+        ];
+
+        for &mode in &[Mode::Standard, Mode::Reference, Mode::Guava] {
+            let ring = mode_ring(100, mode);
+            for (hash, &node) in expected.iter().enumerate() {
+                assert_eq!(ring.get_node_by_hash(hash as u64), node);
+            }
+            for &(hash, nodes, node) in &cases {
+                assert_eq!(mode_ring(nodes, mode).get_node_by_hash(hash), node);
+            }
+        }
+    }
+
+    // Values where the modes disagree, generated by running the reference C++ from the paper
+    // compiled with g++ and Guava's `consistentHash` on the JVM.
+    #[test]
+    fn test_get_node_by_hash_modes() {
+        let cases = [
+            (
+                15_127_819_295_737_311_633,
+                1_486_524_564,
+                862_118_943,
+                862_118_944,
+            ),
+            (
+                6_583_411_529_185_908_483,
+                2_114_996_948,
+                1_773_347_304,
+                1_773_347_302,
+            ),
+            (
+                5_271_766_905_296_825_770,
+                1_395_840_463,
+                1_002_146_376,
+                1_002_146_351,
+            ),
+            (
+                13_418_127_877_345_053_114,
+                1_146_655_274,
+                1_122_512_200,
+                1_122_512_201,
+            ),
+            (
+                12_970_273_959_431_459_788,
+                1_063_824_052,
+                555_466_842,
+                555_465_177,
+            ),
+            (2_687_259_751_451_158_933, 1_661_569_689, 195, 844_379_201),
+        ];
+
+        for &(hash, nodes, guava, reference) in &cases {
+            assert_eq!(mode_ring(nodes, Mode::Guava).get_node_by_hash(hash), guava);
+            assert_eq!(
+                mode_ring(nodes, Mode::Reference).get_node_by_hash(hash),
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_node_by_hash_large() {
+        let cases = [
+            (10_863_919_174_838_991, 76756, 977_315_119),
+            (2_016_238_256_797_177_309, 39755, 163_632_596),
+            (1_673_758_223_894_951_030, 75305, 86_238_366),
+            (u64::MAX, 18311, 699_554_662),
+            (0xDEAD_BEEF_CAFE_BABE, 71225, 635_109_204),
+        ];
+
+        for &mode in &[Mode::Reference, Mode::Guava] {
+            for &(hash, small, large) in &cases {
+                assert_eq!(mode_ring(100_001, mode).get_node_by_hash(hash), small);
+                assert_eq!(mode_ring(1 << 30, mode).get_node_by_hash(hash), large);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_node_by_hash() {
+        let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
+        for point in 0..100 {
+            let hash = BuildDefaultHasher::default().hash_one(point);
+            assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&point));
+            assert_eq!(ring.get_nodes_by_hash(hash, 3), ring.get_nodes(&point, 3));
+        }
+    }
+
+    #[test]
+    fn test_get_node_by_hash_u64_nodes() {
+        let nodes = 1 << 40;
+        for &mode in &[Mode::Standard, Mode::Reference] {
+            let ring = mode_ring(nodes, mode);
+            let mut max = 0;
+            for hash in 0..1000 {
+                max = cmp::max(max, ring.get_node_by_hash(hash));
+            }
+            assert!(max > u64::from(u32::MAX) && max < nodes);
+            assert_eq!(ring.get_nodes_by_hash(0, 2)[0], ring.get_node_by_hash(0));
+        }
+    }
+
+    #[test]
+    fn test_try_with_mode_invalid_capacity() {
+        let ring = Ring::try_with_mode(BuildDefaultHasher::default(), 1 << 31, Mode::Guava);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+
+        let ring = Ring::try_with_hasher(BuildDefaultHasher::default(), u64::MAX);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+
+        let ring = Ring::try_with_mode(BuildDefaultHasher::default(), 1 << 31, Mode::Reference);
+        assert_eq!(ring.map(|ring| ring.mode()), Ok(Mode::Reference));
+    }
+
+    #[test]
+    fn test_get_nodes_mode() {
+        let ring = mode_ring(10, Mode::Guava);
+        let mut nodes = ring.get_nodes(&"foo", 20);
+        assert_eq!(nodes[0], ring.get_node(&"foo"));
+
+        nodes.sort();
+        assert_eq!(nodes, (0..10).collect::<Vec<u64>>());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_mode() {
+        let ring = mode_ring(100, Mode::Guava);
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.mode(), Mode::Guava);
+
+        let json = r#"{"nodes":100}"#;
+        let other: Ring<BuildDefaultHasher> = serde_json::from_str(json).unwrap();
+        assert_eq!(other.mode(), Mode::Standard);
+
+        let json = r#"{"nodes":2147483648,"mode":"Guava"}"#;
+        assert!(serde_json::from_str::<Ring<BuildDefaultHasher>>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.nodes(), 100);
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+
+        let json = r#"{"nodes":0}"#;
+        assert!(serde_json::from_str::<Ring<BuildDefaultHasher>>(json).is_err());
+    }
+
+    #[test]
+    fn test_named_get_node() {
+        let nodes: Vec<String> = (0..100).map(|i| format!("node-{}", i)).collect();
+        let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
+        let named = NamedRing::with_hasher(BuildDefaultHasher::default(), nodes.clone());
+
+        for point in 0..100 {
+            let bucket = ring.get_node(&point) as usize;
+            assert_eq!(named.get_node(&point), &nodes[bucket]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_named_get_node_empty_ring() {
+        let ring: NamedRing<u32, _> = NamedRing::with_hasher(BuildDefaultHasher::default(), vec![]);
+        ring.get_node(&0);
+    }
+
+    #[test]
+    fn test_named_try_get_node() {
+        let mut ring = NamedRing::with_hasher(BuildDefaultHasher::default(), vec![]);
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+
+        ring.push_node(0);
+        assert_eq!(ring.try_get_node(&0), Ok(&0));
+        assert_eq!(ring.pop_node(), Some(0));
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+    }
+
+    #[test]
+    fn test_named_push_and_pop_node() {
+        let mut ring = NamedRing::with_hasher(BuildDefaultHasher::default(), (0..10).collect());
+        let before: Vec<u32> = (0..1000).map(|point| *ring.get_node(&point)).collect();
+
+        ring.push_node(10);
+        let mut moved = 0;
+        for point in 0..1000 {
+            let node = *ring.get_node(&point);
+            if node != before[point as usize] {
+                assert_eq!(node, 10);
+                moved += 1;
+            }
+        }
+        assert!(moved > 50 && moved < 150);
+
+        assert_eq!(ring.pop_node(), Some(10));
+        for point in 0..1000 {
+            assert_eq!(ring.get_node(&point), &before[point as usize]);
+        }
+
+        assert_eq!(ring.pop_node(), Some(9));
+        for point in 0..1000 {
+            if before[point as usize] != 9 {
+                assert_eq!(ring.get_node(&point), &before[point as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_named_get_nodes() {
+        let ring = NamedRing::with_hasher(BuildDefaultHasher::default(), (10..20).collect());
+        let buckets = Ring::with_hasher(BuildDefaultHasher::default(), 10).get_nodes(&"foo", 20);
+        let nodes = ring.get_nodes(&"foo", 20);
+
+        assert_eq!(nodes.len(), 10);
+        for (node, bucket) in nodes.iter().zip(buckets) {
+            assert_eq!(**node, bucket + 10);
+        }
+    }
+
+    #[test]
+    fn test_named_iter() {
+        let ring = NamedRing::with_hasher(BuildDefaultHasher::default(), vec![0, 1]);
+        assert_eq!(ring.len(), 2);
+        assert!(!ring.is_empty());
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [&0, &1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_named_serde() {
+        let ring = NamedRing::with_hasher(BuildDefaultHasher::default(), (0..10).collect());
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: NamedRing<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.nodes, ring.nodes);
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+    }
 }
@@ -0,0 +1,837 @@
+//! Hashing ring implemented using ketama consistent hashing.
+
+use crate::Error;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::collections::HashSet;
+
+const POINTS_PER_NODE: u32 = 160;
+const POINTS_PER_DIGEST: u32 = 4;
+
+/// Returns the 32-bit ketama hash of a key, which is the first four bytes of its MD5 digest
+/// interpreted as a little-endian integer.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::ketama;
+///
+/// assert_eq!(ketama::hash(&"a"), 0xB975_C10C);
+/// ```
+pub fn hash<U>(key: &U) -> u32
+where
+    U: AsRef<[u8]> + ?Sized,
+{
+    let digest = md5::compute(key.as_ref());
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// A hashing ring implemented using ketama consistent hashing.
+///
+/// Ketama is the consistent hashing scheme of libketama and of libmemcached's weighted ketama
+/// distribution. Each node is identified by a string, typically `"host:port"`, and for every
+/// digest index `i` the MD5 digest of `"{id}-{i}"` is split into four little-endian 32-bit points.
+/// Nodes receive 160 points per share of the total weight, so nodes of equal weight receive 160
+/// points each. A point is mapped to the node with the smallest point that is greater than or
+/// equal to the ketama hash of the point's bytes, wrapping around to the smallest point.
+///
+/// Since points are hashed by their bytes rather than through `Hash`, `Ring` does not implement
+/// `HashRing`. Note that libmemcached omits the port from the identifier of servers using the
+/// default port of 11211, so such servers should be inserted as `"host"` to agree with it.
+///
+/// # Examples
+/// ```
+/// use hash_rings::ketama::Ring;
+///
+/// let mut ring = Ring::new();
+///
+/// ring.insert_node("127.0.0.1:11211", 1);
+/// ring.insert_node("127.0.0.1:11212", 1);
+/// ring.insert_node("127.0.0.1:11213", 1);
+///
+/// ring.remove_node(&"127.0.0.1:11211");
+///
+/// assert_eq!(ring.get_node(&"foo"), &"127.0.0.1:11213");
+/// assert_eq!(ring.len(), 2);
+///
+/// let mut iterator = ring.iter();
+/// assert_eq!(iterator.next(), Some((&"127.0.0.1:11212", 1)));
+/// assert_eq!(iterator.next(), Some((&"127.0.0.1:11213", 1)));
+/// assert_eq!(iterator.next(), None);
+/// ```
+#[derive(Clone)]
+pub struct Ring<T> {
+    nodes: Vec<(T, u32)>,
+    continuum: Vec<(u32, usize)>,
+}
+
+impl<T> Ring<T> {
+    /// Constructs a new, empty `Ring<T>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            continuum: Vec::new(),
+        }
+    }
+
+    fn rebuild(&mut self)
+    where
+        T: AsRef<str>,
+    {
+        // The number of points is computed with single precision floats to agree with libmemcached.
+        let total_weight = self.nodes.iter().map(|node| u64::from(node.1)).sum::<u64>() as f32;
+        let len = self.nodes.len() as f32;
+        self.continuum.clear();
+        for (index, (id, weight)) in self.nodes.iter().enumerate() {
+            let pct = *weight as f32 / total_weight;
+            let digests = (f64::from(pct * POINTS_PER_NODE as f32 / POINTS_PER_DIGEST as f32 * len)
+                + 0.000_000_000_1)
+                .floor() as u32;
+            for i in 0..digests {
+                let digest = md5::compute(format!("{}-{}", id.as_ref(), i));
+                for chunk in digest.chunks(4) {
+                    let point = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                    self.continuum.push((point, index));
+                }
+            }
+        }
+        self.continuum.sort();
+    }
+
+    fn get_index(&self, hash: u32) -> usize {
+        let index = self.continuum.partition_point(|&(point, _)| point < hash);
+        if index == self.continuum.len() {
+            0
+        } else {
+            index
+        }
+    }
+
+    /// Inserts a node into the ring with a particular weight. If the node already exists, its
+    /// weight is updated.
+    ///
+    /// Since the number of points of every node depends on the total weight of the ring, the
+    /// points of all nodes are regenerated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// // "127.0.0.1:11212" will receive approximately three times more points than
+    /// // "127.0.0.1:11211"
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.insert_node("127.0.0.1:11212", 3);
+    /// ```
+    pub fn insert_node(&mut self, id: T, weight: u32)
+    where
+        T: AsRef<str> + Eq,
+    {
+        if let Err(err) = self.try_insert_node(id, weight) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Inserts a node into the ring with a particular weight.
+    ///
+    /// Returns `Error::InvalidWeight` if the weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// assert_eq!(ring.try_insert_node("127.0.0.1:11211", 1), Ok(()));
+    /// assert_eq!(ring.try_insert_node("127.0.0.1:11212", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, weight: u32) -> Result<(), Error>
+    where
+        T: AsRef<str> + Eq,
+    {
+        if weight == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        match self.nodes.iter().position(|node| node.0 == id) {
+            Some(index) => self.nodes[index].1 = weight,
+            None => self.nodes.push((id, weight)),
+        }
+        self.rebuild();
+        Ok(())
+    }
+
+    /// Removes a node from the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.remove_node(&"127.0.0.1:11211");
+    /// ```
+    pub fn remove_node(&mut self, id: &T)
+    where
+        T: AsRef<str> + Eq,
+    {
+        if let Err(err) = self.try_remove_node(id) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Removes a node from the ring.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.try_remove_node(&"127.0.0.1:11211"), Ok(()));
+    /// assert_eq!(ring.try_remove_node(&"127.0.0.1:11211"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<(), Error>
+    where
+        T: AsRef<str> + Eq,
+    {
+        let index = self
+            .nodes
+            .iter()
+            .position(|node| &node.0 == id)
+            .ok_or(Error::UnknownNode)?;
+        self.nodes.remove(index);
+        self.rebuild();
+        Ok(())
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.get_node(&"foo"), &"127.0.0.1:11211");
+    /// ```
+    pub fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: AsRef<[u8]> + ?Sized,
+    {
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node(&"foo"), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.try_get_node(&"foo"), Ok(&"127.0.0.1:11211"));
+    /// ```
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: AsRef<[u8]> + ?Sized,
+    {
+        self.try_get_node_by_hash(hash(point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are found by walking clockwise around the continuum from the point and skipping
+    /// the points of nodes that were already returned, so the first node is the node returned by
+    /// `get_node`. Fewer than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.insert_node("127.0.0.1:11212", 1);
+    ///
+    /// let nodes = ring.get_nodes(&"foo", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"foo"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: AsRef<[u8]> + ?Sized,
+    {
+        self.get_nodes_by_hash(hash(point), n)
+    }
+
+    /// Returns the node associated with the ketama hash of a point. Ketama hashes are 32-bit, so
+    /// `get_node_by_hash` returns the same node as `get_node` when given the value returned by
+    /// `ketama::hash` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::{self, Ring};
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.insert_node("127.0.0.1:11212", 1);
+    ///
+    /// let hash = ketama::hash(&"foo");
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"foo"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u32) -> &T {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with the ketama hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"127.0.0.1:11211"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u32) -> Result<&T, Error> {
+        if self.continuum.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        let (_, index) = self.continuum[self.get_index(hash)];
+        Ok(&self.nodes[index].0)
+    }
+
+    /// Returns up to `n` distinct nodes associated with the ketama hash of a point in order of
+    /// preference. The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::{self, Ring};
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.insert_node("127.0.0.1:11212", 1);
+    ///
+    /// let hash = ketama::hash(&"foo");
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"foo", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u32, n: usize) -> Vec<&T> {
+        if self.continuum.is_empty() {
+            return Vec::new();
+        }
+        let start = self.get_index(hash);
+        let n = cmp::min(n, self.nodes.len());
+        let mut seen = HashSet::with_capacity(n);
+        let mut ret = Vec::with_capacity(n);
+        let (tail, head) = self.continuum.split_at(start);
+        for &(_, index) in head.iter().chain(tail) {
+            if ret.len() == n {
+                break;
+            }
+            if seen.insert(index) {
+                ret.push(&self.nodes[index].0);
+            }
+        }
+        ret
+    }
+
+    /// Returns the nodes associated with a batch of points in the order of the points.
+    ///
+    /// The hashes of the points are sorted so that the continuum is walked once for the whole
+    /// batch instead of searched once for each point, which is faster than calling `get_node` for
+    /// each point when the batch is large relative to the number of points in the continuum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// ring.insert_node("127.0.0.1:11212", 1);
+    ///
+    /// let points = ["point-1", "point-2", "point-3"];
+    /// let nodes = ring.get_nodes_batch(&points);
+    /// for (point, node) in points.iter().zip(nodes) {
+    ///     assert_eq!(node, ring.get_node(point));
+    /// }
+    /// ```
+    pub fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: AsRef<[u8]>,
+    {
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let first = match self.continuum.first() {
+            Some(&(_, index)) => &self.nodes[index].0,
+            None => panic!("Error: {}.", Error::EmptyRing),
+        };
+
+        let mut hashes: Vec<(u32, usize)> = points.iter().map(hash).zip(0..).collect();
+        hashes.sort_unstable();
+
+        // Points with a larger hash than every point in the continuum wrap around to the first
+        // point.
+        let mut ret = vec![first; points.len()];
+        let mut continuum = self.continuum.iter().peekable();
+        for (hash, index) in hashes {
+            while continuum.next_if(|entry| entry.0 < hash).is_some() {}
+            match continuum.peek() {
+                Some(entry) => ret[index] = &self.nodes[entry.1].0,
+                None => break,
+            }
+        }
+        ret
+    }
+
+    /// Returns an iterator over the continuum of the ring. The iterator will yield the points of
+    /// the ring and their nodes in increasing order of points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.iter_points().count(), 160);
+    /// ```
+    pub fn iter_points(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.continuum
+            .iter()
+            .map(move |&(point, index)| (point, &self.nodes[index].0))
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert_eq!(ring.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// assert!(ring.is_empty());
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    /// assert!(!ring.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield nodes and their weights in the
+    /// order in which the nodes were inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::ketama::Ring;
+    ///
+    /// let mut ring = Ring::new();
+    /// ring.insert_node("127.0.0.1:11211", 1);
+    ///
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some((&"127.0.0.1:11211", 1)));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u32)> {
+        self.nodes.iter().map(|node| (&node.0, node.1))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Ring<T> {
+    type IntoIter = Box<dyn Iterator<Item = (&'a T, u32)> + 'a>;
+    type Item = (&'a T, u32);
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+impl<T> Default for Ring<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serializes the ids and weights of the nodes in the ring.
+#[cfg(feature = "serde")]
+impl<T> Serialize for Ring<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: Vec<(&'a T, u32)>,
+        }
+
+        State {
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and weights of its nodes. The continuum is regenerated, so
+/// lookups are preserved.
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for Ring<T>
+where
+    T: Deserialize<'de> + AsRef<str> + Eq,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            nodes: Vec<(T, u32)>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut ring = Self::new();
+        for (id, weight) in state.nodes {
+            ring.try_insert_node(id, weight)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash, Ring};
+    use crate::Error;
+
+    // The weighted servers of libketama's `ketama.servers` file. libmemcached's ketama tests add
+    // them on the default port of 11211, so they are hashed without the port.
+    const SERVERS: [(&str, u32); 8] = [
+        ("10.0.1.1", 600),
+        ("10.0.1.2", 300),
+        ("10.0.1.3", 200),
+        ("10.0.1.4", 350),
+        ("10.0.1.5", 1000),
+        ("10.0.1.6", 800),
+        ("10.0.1.7", 950),
+        ("10.0.1.8", 100),
+    ];
+
+    #[test]
+    fn test_hash() {
+        // The first four bytes of the MD5 test suite digests in RFC 1321.
+        assert_eq!(hash(""), 0xD98C_1DD4);
+        assert_eq!(hash("a"), 0xB975_C10C);
+        assert_eq!(hash("abc"), 0x9850_0190);
+        assert_eq!(hash("message digest"), 0x7D69_6BF9);
+    }
+
+    // Returns the continuum of libmemcached's weighted ketama distribution, which hashes
+    // `"{host}-{i}"` for every digest index `i` of a server.
+    fn continuum(servers: &[(&'static str, u32)], digests: &[u32]) -> Vec<(u32, &'static str)> {
+        let mut continuum = Vec::new();
+        for (&(id, _), &digests) in servers.iter().zip(digests) {
+            for i in 0..digests {
+                let digest = md5::compute(format!("{}-{}", id, i));
+                for chunk in digest.chunks(4) {
+                    let point = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+                    continuum.push((point, id));
+                }
+            }
+        }
+        continuum.sort_unstable();
+        continuum
+    }
+
+    // Asserts that every key is mapped to the server with the first point of the continuum that is
+    // not less than the hash of the key, wrapping around to the first point.
+    fn assert_lookups(ring: &Ring<&str>, continuum: &[(u32, &str)]) {
+        for key in 0..1000 {
+            let key = format!("key-{}", key);
+            let hash = hash(&key);
+            let expected = continuum
+                .iter()
+                .find(|point| point.0 >= hash)
+                .unwrap_or(&continuum[0])
+                .1;
+            assert_eq!(ring.get_node(&key), &expected);
+        }
+    }
+
+    #[test]
+    fn test_points_per_node() {
+        let mut ring = Ring::new();
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(id, weight);
+        }
+
+        // libmemcached gives each server `floor(weight / 4300 * 160 / 4 * 8)` digests of four
+        // points each.
+        let digests: [u32; 8] = [44, 22, 14, 26, 74, 59, 70, 7];
+        let counts: Vec<usize> = SERVERS
+            .iter()
+            .map(|&(id, _)| ring.iter_points().filter(|point| point.1 == &id).count())
+            .collect();
+        let expected: Vec<usize> = digests
+            .iter()
+            .map(|&digests| 4 * digests as usize)
+            .collect();
+        assert_eq!(counts, expected);
+        assert_eq!(ring.iter_points().count(), 1264);
+
+        let continuum = continuum(&SERVERS, &digests);
+        assert!(ring
+            .iter_points()
+            .eq(continuum.iter().map(|(point, id)| (*point, id))));
+    }
+
+    #[test]
+    fn test_get_node_weighted() {
+        let mut ring = Ring::new();
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(id, weight);
+        }
+        assert_lookups(
+            &ring,
+            &continuum(&SERVERS, &[44, 22, 14, 26, 74, 59, 70, 7]),
+        );
+    }
+
+    #[test]
+    fn test_get_node_unweighted() {
+        // libmemcached omits the port of servers that use the default port of 11211.
+        let servers = [
+            ("127.0.0.1", 1),
+            ("127.0.0.1:11212", 1),
+            ("127.0.0.1:11213", 1),
+        ];
+        let mut ring = Ring::new();
+        for &(id, weight) in servers.iter() {
+            ring.insert_node(id, weight);
+        }
+        assert_eq!(ring.iter_points().count(), 480);
+        assert_lookups(&ring, &continuum(&servers, &[40, 40, 40]));
+    }
+
+    #[test]
+    fn test_get_node_non_default_port() {
+        // libmemcached hashes `"{host}:{port}-{i}"` for servers that do not use port 11211.
+        let mut ring = Ring::new();
+        ring.insert_node("127.0.0.1:11212", 1);
+
+        let mut expected = Vec::new();
+        for i in 0..40 {
+            let digest = md5::compute(format!("127.0.0.1:11212-{}", i));
+            for chunk in digest.chunks(4) {
+                expected.push(u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+            }
+        }
+        expected.sort_unstable();
+
+        let points: Vec<u32> = ring.iter_points().map(|point| point.0).collect();
+        assert_eq!(points, expected);
+        assert_eq!(ring.get_node_by_hash(expected[0]), &"127.0.0.1:11212");
+    }
+
+    #[test]
+    fn test_insert_node_replace() {
+        let mut ring = Ring::new();
+        ring.insert_node("127.0.0.1:11211", 1);
+        ring.insert_node("127.0.0.1:11212", 1);
+        ring.insert_node("127.0.0.1:11211", 3);
+
+        assert_eq!(ring.len(), 2);
+        assert_eq!(
+            ring.iter().collect::<Vec<_>>(),
+            [(&"127.0.0.1:11211", 3), (&"127.0.0.1:11212", 1)]
+        );
+        assert_eq!(ring.iter_points().count(), 320);
+    }
+
+    #[test]
+    fn test_try_empty_ring() {
+        let mut ring: Ring<&str> = Ring::new();
+        assert_eq!(ring.try_get_node("foo"), Err(Error::EmptyRing));
+        assert_eq!(
+            ring.try_remove_node(&"127.0.0.1:11211"),
+            Err(Error::UnknownNode)
+        );
+        assert_eq!(
+            ring.try_insert_node("127.0.0.1:11211", 0),
+            Err(Error::InvalidWeight)
+        );
+        assert!(ring.get_nodes("foo", 2).is_empty());
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_get_node_empty_ring() {
+        let ring: Ring<&str> = Ring::new();
+        ring.get_node("foo");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_insert_node_zero_weight() {
+        let mut ring = Ring::new();
+        ring.insert_node("127.0.0.1", 0);
+    }
+
+    #[test]
+    fn test_get_nodes() {
+        let mut ring = Ring::new();
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(id, weight);
+        }
+
+        for point in 0..100 {
+            let point = point.to_string();
+            let mut nodes = ring.get_nodes(&point, 10);
+            assert_eq!(nodes[0], ring.get_node(&point));
+            assert_eq!(ring.get_nodes(&point, 3)[..], nodes[..3]);
+
+            nodes.sort();
+            nodes.dedup();
+            assert_eq!(nodes.len(), SERVERS.len());
+        }
+    }
+
+    #[test]
+    fn test_get_nodes_batch() {
+        let mut ring = Ring::new();
+        assert!(ring.get_nodes_batch::<String>(&[]).is_empty());
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(id, weight);
+        }
+
+        let points: Vec<String> = (0..1000).map(|point| point.to_string()).collect();
+        let nodes = ring.get_nodes_batch(&points);
+        for (point, node) in points.iter().zip(nodes) {
+            assert_eq!(node, ring.get_node(point));
+        }
+    }
+
+    #[test]
+    fn test_get_node_by_hash() {
+        let mut ring = Ring::new();
+        assert_eq!(ring.try_get_node_by_hash(0), Err(Error::EmptyRing));
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(id, weight);
+        }
+
+        for point in 0..100 {
+            let point = point.to_string();
+            let point_hash = hash(&point);
+            assert_eq!(ring.get_node_by_hash(point_hash), ring.get_node(&point));
+            assert_eq!(
+                ring.get_nodes_by_hash(point_hash, 3),
+                ring.get_nodes(&point, 3)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut ring = Ring::new();
+        for &(id, weight) in SERVERS.iter() {
+            ring.insert_node(String::from(id), weight);
+        }
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<String> = serde_json::from_str(&json).unwrap();
+
+        assert!(ring.iter_points().eq(other.iter_points()));
+
+        let json = r#"{"nodes":[["127.0.0.1:11211",0]]}"#;
+        assert!(serde_json::from_str::<Ring<String>>(json).is_err());
+    }
+}
@@ -7,11 +7,14 @@
 //! [![Build Status](https://travis-ci.org/jeffrey-xiao/hash-rings-rs.svg?branch=master)](https://travis-ci.org/jeffrey-xiao/hash-rings-rs)
 //! [![codecov](https://codecov.io/gh/jeffrey-xiao/hash-rings-rs/branch/master/graph/badge.svg)](https://codecov.io/gh/jeffrey-xiao/hash-rings-rs)
 //!
-//! `hash-rings` contains implementations for seven different hash ring algorithms: Cache Array
-//! Routing Protocol, Consistent Hashing, Multi-Probe Consistent Hashing, Rendezvous Hashing,
-//! Weighted Rendezvous Hashing, Maglev Hashing, and Jump Hashing. It also provides clients for
-//! Consistent Hashing, Rendezvous Hashing, and Weighted Rendezvous Hashing to efficiently
-//! redistribute items as nodes are inserted and removed from the ring.
+//! `hash-rings` contains implementations for nine different hash ring algorithms: Cache Array
+//! Routing Protocol, Consistent Hashing, Ketama Consistent Hashing, Multi-Probe Consistent Hashing,
+//! Rendezvous Hashing, Weighted Rendezvous Hashing, Maglev Hashing, Jump Hashing, and Memento
+//! Hashing. It also provides clients for Consistent Hashing, Rendezvous Hashing, and Weighted
+//! Rendezvous Hashing to efficiently redistribute items as nodes are inserted and removed from the
+//! ring, as well as a Consistent Hashing client with bounded loads that caps the number of items
+//! associated with each node. Any ring can be shared between threads with `SharedRing`, which
+//! publishes immutable snapshots of the ring so that readers never block on writers.
 //!
 //! ## Examples
 //!
@@ -27,8 +30,8 @@
 //! fn main() {
 //!     let mut r = Ring::with_hasher(DefaultBuildHasher::default());
 //!
-//!     r.insert_node(&"node-1", 1);
-//!     r.insert_node(&"node-2", 3);
+//!     r.insert_node("node-1", 1);
+//!     r.insert_node("node-2", 3);
 //!
 //!     assert_eq!(r.get_node(&"point-1"), &"node-1");
 //! }
@@ -45,16 +48,60 @@
 //!
 //! fn main() {
 //!     let mut c = Client::with_hasher(DefaultBuildHasher::default());
-//!     c.insert_node(&"node-1", 1);
-//!     c.insert_node(&"node-2", 3);
+//!     c.insert_node("node-1", 1);
+//!     c.insert_node("node-2", 3);
 //!
-//!     c.insert_point(&"point-1");
+//!     c.insert_point("point-1");
 //!
 //!     assert_eq!(c.get_node(&"point-1"), &"node-1");
 //!     assert_eq!(c.get_points(&"node-1"), [&"point-1"]);
 //! }
 //! ```
 //!
+//! ### Example Generic Usage
+//!
+//! All rings implement `HashRing`, and rings that support inserting and removing nodes in place also
+//! implement `MutableHashRing`.
+//!
+//! ```rust
+//! use hash_rings::{consistent, rendezvous, MutableHashRing};
+//!
+//! fn build<R: MutableHashRing<Weight = usize>>(ring: &mut R, ids: Vec<R::Id>) {
+//!     for id in ids {
+//!         ring.insert_node(id, 1);
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let mut r: consistent::Ring<&str> = consistent::Ring::new();
+//!     build(&mut r, vec!["node-1", "node-2"]);
+//!     assert_eq!(r.len(), 2);
+//!
+//!     let mut r: rendezvous::Ring<&str> = rendezvous::Ring::new();
+//!     build(&mut r, vec!["node-1", "node-2"]);
+//!     assert_eq!(r.len(), 2);
+//! }
+//! ```
+//!
+//! ### Example Error Handling
+//!
+//! Methods that panic on an empty ring, an unknown node, or an invalid weight or capacity have
+//! `try_*` counterparts that return a `hash_rings::Error` instead.
+//!
+//! ```rust
+//! use hash_rings::consistent::Client;
+//! use hash_rings::Error;
+//!
+//! fn main() {
+//!     let mut c: Client<&str, &str> = Client::new();
+//!     assert_eq!(c.try_insert_point("point-1"), Err(Error::EmptyRing));
+//!
+//!     c.insert_node("node-1", 1);
+//!     assert_eq!(c.try_remove_node(&"node-2"), Err(Error::UnknownNode));
+//!     assert_eq!(c.try_remove_node(&"node-1"), Err(Error::EmptyRing));
+//! }
+//! ```
+//!
 //! ## Usage
 //!
 //! Add this to your `Cargo.toml`:
@@ -70,6 +117,18 @@
 //! extern crate hash_rings;
 //! ```
 //!
+//! Rings and clients can be serialized and deserialized with [Serde](https://serde.rs) by enabling
+//! the `serde` feature:
+//!
+//! ```toml
+//! [dependencies]
+//! hash-rings = { version = "*", features = ["serde"] }
+//! ```
+//!
+//! Only the membership of a ring is serialized, such as node ids, replicas, weights, and
+//! capacities. The hash builder is not serialized and the ring is rebuilt with `H::default()` on
+//! load, so lookups are preserved with the default `StableBuildHasher` but not with `RandomState`.
+//!
 //! ## Benchmarks
 //!
 //! ```text
@@ -225,11 +284,22 @@
 
 pub mod carp;
 pub mod consistent;
+mod error;
+mod hasher;
 pub mod jump;
+pub mod ketama;
 pub mod maglev;
+pub mod memento;
 pub mod mpc;
 pub mod rendezvous;
+mod ring;
+mod shared;
 #[cfg(test)]
 mod test_util;
 mod util;
 pub mod weighted_rendezvous;
+
+pub use crate::error::Error;
+pub use crate::hasher::StableBuildHasher;
+pub use crate::ring::{HashRing, MutableHashRing};
+pub use crate::shared::SharedRing;
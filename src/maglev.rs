@@ -1,51 +1,189 @@
 //! Hashing ring implemented using maglev hashing.
 
-use primal::Sieve;
-use rand::{Rng, XorShiftRng};
-use siphasher::sip::SipHasher;
-use std::hash::{Hash, Hasher};
+use crate::util;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::iter;
+use std::mem;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use twox_hash::XxHash64;
+
+/// The default size of the lookup table of Envoy's maglev load balancer.
+pub const ENVOY_TABLE_SIZE: usize = 65537;
+
+// Returns the smallest prime greater than or equal to `n`.
+fn next_prime(n: usize) -> usize {
+    (n.max(2)..)
+        .find(|i| primal::is_prime(*i as u64))
+        .expect("Expected a prime larger than or equal to `capacity_hint`.")
+}
+
+// An index of a node in a lookup table. `EMPTY` marks entries that have not been claimed.
+trait Entry: Copy + Eq {
+    const EMPTY: Self;
+
+    fn from_index(index: usize) -> Self;
+
+    fn index(self) -> usize;
+}
+
+impl Entry for u16 {
+    const EMPTY: Self = u16::MAX;
+
+    fn from_index(index: usize) -> Self {
+        index as u16
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Entry for u32 {
+    const EMPTY: Self = u32::MAX;
+
+    fn from_index(index: usize) -> Self {
+        index as u32
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// A lookup table that stores the indices of nodes in the smallest integer type that fits them.
+#[derive(Clone, Debug, PartialEq)]
+enum Lookup {
+    Small(Vec<u16>),
+    Large(Vec<u32>),
+}
+
+impl Lookup {
+    // Fills a lookup table of size `m` in rounds starting from round one. In every round, each
+    // node for which `claims(node, round)` returns `true` claims the next entry in its permutation
+    // that has not been claimed.
+    fn populate<F>(permutations: &[(usize, usize)], m: usize, claims: F) -> Self
+    where
+        F: FnMut(usize, u64) -> bool,
+    {
+        if permutations.len() < u16::MAX as usize {
+            Lookup::Small(Self::populate_entries(permutations, m, claims))
+        } else {
+            assert!(
+                permutations.len() < u32::MAX as usize,
+                "Expected fewer than `u32::MAX` nodes.",
+            );
+            Lookup::Large(Self::populate_entries(permutations, m, claims))
+        }
+    }
+
+    // The `i`-th entry in the permutation of a node is `(offset + i * skip) % m`, so the next
+    // entry is found by adding `skip` to the current one instead of materializing the permutation.
+    fn populate_entries<E, F>(permutations: &[(usize, usize)], m: usize, mut claims: F) -> Vec<E>
+    where
+        E: Entry,
+        F: FnMut(usize, u64) -> bool,
+    {
+        let mut next: Vec<usize> = permutations.iter().map(|&(offset, _)| offset).collect();
+        let mut entry: Vec<E> = vec![E::EMPTY; m];
+
+        let mut i = 0;
+        let mut round = 1;
+        while i < m {
+            for (j, &(_, skip)) in permutations.iter().enumerate() {
+                if !claims(j, round) {
+                    continue;
+                }
+
+                let mut c = next[j];
+                while entry[c] != E::EMPTY {
+                    c = (c + skip) % m;
+                }
+                entry[c] = E::from_index(j);
+                next[j] = (c + skip) % m;
+                i += 1;
+
+                if i == m {
+                    break;
+                }
+            }
+            round += 1;
+        }
+
+        entry
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Lookup::Small(entries) => entries.len(),
+            Lookup::Large(entries) => entries.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> usize {
+        match self {
+            Lookup::Small(entries) => entries[index].index(),
+            Lookup::Large(entries) => entries[index].index(),
+        }
+    }
+
+    fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = usize> + 'a> {
+        match self {
+            Lookup::Small(entries) => Box::new(entries.iter().map(|entry| entry.index())),
+            Lookup::Large(entries) => Box::new(entries.iter().map(|entry| entry.index())),
+        }
+    }
+}
 
 /// A hashing ring implemented using maglev hashing.
 ///
 /// Maglev hashing produces a lookup table that allows finding a node in constant time by
-/// generating random permutations.
+/// generating random permutations. The permutations are derived from the hash builder, so rings
+/// over the same nodes with differently keyed hash builders have uncorrelated lookup tables.
+///
+/// Nodes can be weighted, in which case each node claims entries of the lookup table in proportion
+/// to its weight as in the weighted variant of the Maglev paper. A node with the largest weight
+/// claims an entry in every round, and a node with a third of the largest weight claims an entry
+/// in every third round.
 ///
 /// # Examples
 /// ```
 /// use hash_rings::maglev::Ring;
 ///
-/// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
+/// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
 ///
 /// assert_eq!(ring.get_node(&"point-1"), &"node-3");
 /// assert_eq!(ring.nodes(), 3);
 /// assert_eq!(ring.capacity(), 307);
 /// ```
-pub struct Ring<'a, T> {
-    nodes: Vec<&'a T>,
-    lookup: Vec<usize>,
-    hasher: SipHasher,
+#[derive(Clone)]
+pub struct Ring<T, H = StableBuildHasher> {
+    nodes: Vec<T>,
+    weights: Vec<u32>,
+    lookup: Lookup,
+    permutations: Vec<(usize, usize)>,
+    hash_builder: H,
 }
 
-impl<'a, T> Ring<'a, T> {
-    fn get_hashers() -> [SipHasher; 2] {
-        let mut rng = XorShiftRng::new_unseeded();
-        [
-            SipHasher::new_with_keys(rng.next_u64(), rng.next_u64()),
-            SipHasher::new_with_keys(rng.next_u64(), rng.next_u64()),
-        ]
-    }
-
+impl<T> Ring<T, StableBuildHasher> {
     /// Constructs a new `Ring<T>` with a specified list of nodes.
     ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty.
+    ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::maglev::Ring;
     ///
-    /// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
     /// ```
-    pub fn new(nodes: Vec<&'a T>) -> Self
+    pub fn new(nodes: Vec<T>) -> Self
     where
         T: Hash,
     {
@@ -54,82 +192,312 @@ impl<'a, T> Ring<'a, T> {
         Ring::with_capacity_hint(nodes, capacity_hint)
     }
 
+    /// Constructs a new `Ring<T>` with a specified list of nodes.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(Ring::try_new(vec!["node-1", "node-2", "node-3"]).is_ok());
+    /// assert_eq!(Ring::<&str>::try_new(vec![]).err(), Some(Error::EmptyRing));
+    /// ```
+    pub fn try_new(nodes: Vec<T>) -> Result<Self, Error>
+    where
+        T: Hash,
+    {
+        let capacity_hint = nodes.len() * 100;
+        Ring::try_with_capacity_hint(nodes, capacity_hint)
+    }
+
     /// Constructs a new `Ring<T>` with a specified list of nodes and a capacity hint. The actual
     /// capacity of the ring will always be the next prime greater than or equal to
     /// `capacity_hint`. If nodes are removed and the ring is regenerated, the ring should be
-    /// rebuilt with the same capacity.
+    /// rebuilt with the same capacity using `rebuild`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty.
     ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::maglev::Ring;
     ///
-    /// let ring = Ring::with_capacity_hint(vec![&"node-1", &"node-2", &"node-3"], 100);
+    /// let ring = Ring::with_capacity_hint(vec!["node-1", "node-2", "node-3"], 100);
     /// assert_eq!(ring.capacity(), 101);
     /// ```
-    pub fn with_capacity_hint(nodes: Vec<&'a T>, capacity_hint: usize) -> Self
+    pub fn with_capacity_hint(nodes: Vec<T>, capacity_hint: usize) -> Self
     where
         T: Hash,
     {
-        let hashers = Self::get_hashers();
-        let lookup = Self::populate(&hashers, &nodes, capacity_hint);
-        Self {
-            nodes,
-            lookup,
-            hasher: hashers[0],
+        Self::with_hasher(Default::default(), nodes, capacity_hint)
+    }
+
+    /// Constructs a new `Ring<T>` with a specified list of nodes and a capacity hint.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty and `Error::InvalidCapacity` if
+    /// `capacity_hint` is less than the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let ring = Ring::try_with_capacity_hint(vec!["node-1", "node-2", "node-3"], 100);
+    /// assert_eq!(ring.map(|ring| ring.capacity()), Ok(101));
+    ///
+    /// let ring = Ring::try_with_capacity_hint(vec!["node-1", "node-2", "node-3"], 2);
+    /// assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_capacity_hint(nodes: Vec<T>, capacity_hint: usize) -> Result<Self, Error>
+    where
+        T: Hash,
+    {
+        Self::try_with_hasher(Default::default(), nodes, capacity_hint)
+    }
+
+    /// Constructs a new `Ring<T>` with a specified list of nodes with weights and a capacity hint.
+    /// The actual capacity of the ring will always be the next prime greater than or equal to
+    /// `capacity_hint`.
+    ///
+    /// Each node is associated with a number of entries in the lookup table that is proportional
+    /// to its weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty or if a weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// // "node-2" will receive three times more points than "node-1"
+    /// let ring = Ring::with_weights(vec![("node-1", 1), ("node-2", 3)], 100);
+    /// assert_eq!(ring.capacity(), 101);
+    /// ```
+    pub fn with_weights(nodes: Vec<(T, u32)>, capacity_hint: usize) -> Self
+    where
+        T: Hash,
+    {
+        Self::with_hasher_and_weights(Default::default(), nodes, capacity_hint)
+    }
+
+    /// Constructs a new `Ring<T>` with a specified list of nodes with weights and a capacity hint.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty, `Error::InvalidWeight` if a weight is zero,
+    /// and `Error::InvalidCapacity` if `capacity_hint` is less than the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let ring = Ring::try_with_weights(vec![("node-1", 1), ("node-2", 3)], 100);
+    /// assert_eq!(ring.map(|ring| ring.capacity()), Ok(101));
+    ///
+    /// let ring = Ring::try_with_weights(vec![("node-1", 1), ("node-2", 0)], 100);
+    /// assert_eq!(ring.err(), Some(Error::InvalidWeight));
+    /// ```
+    pub fn try_with_weights(nodes: Vec<(T, u32)>, capacity_hint: usize) -> Result<Self, Error>
+    where
+        T: Hash,
+    {
+        Self::try_with_hasher_and_weights(Default::default(), nodes, capacity_hint)
+    }
+}
+
+impl<T, H> Ring<T, H> {
+    /// Constructs a new `Ring<T>` with a specified hash builder, list of nodes, and capacity hint.
+    /// The actual capacity of the ring will always be the next prime greater than or equal to
+    /// `capacity_hint`.
+    ///
+    /// The hash builder determines the permutation of each node and the entry of each key in the
+    /// lookup table, so it can be keyed differently to decorrelate rings over the same nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let ring = Ring::with_hasher(
+    ///     StableBuildHasher::with_keys(1, 2),
+    ///     vec!["node-1", "node-2", "node-3"],
+    ///     100,
+    /// );
+    /// assert_eq!(ring.capacity(), 101);
+    /// ```
+    pub fn with_hasher(hash_builder: H, nodes: Vec<T>, capacity_hint: usize) -> Self
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        assert!(!nodes.is_empty());
+        let weights = vec![1; nodes.len()];
+        Self::build(hash_builder, nodes, weights, capacity_hint)
+    }
+
+    /// Constructs a new `Ring<T>` with a specified hash builder, list of nodes, and capacity hint.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty and `Error::InvalidCapacity` if
+    /// `capacity_hint` is less than the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::{Error, StableBuildHasher};
+    ///
+    /// let ring = Ring::try_with_hasher(StableBuildHasher::new(), vec!["node-1"], 100);
+    /// assert_eq!(ring.map(|ring| ring.capacity()), Ok(101));
+    ///
+    /// let ring = Ring::<&str, _>::try_with_hasher(StableBuildHasher::new(), vec![], 100);
+    /// assert_eq!(ring.err(), Some(Error::EmptyRing));
+    /// ```
+    pub fn try_with_hasher(
+        hash_builder: H,
+        nodes: Vec<T>,
+        capacity_hint: usize,
+    ) -> Result<Self, Error>
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        if nodes.is_empty() {
+            return Err(Error::EmptyRing);
         }
+        if capacity_hint < nodes.len() {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self::with_hasher(hash_builder, nodes, capacity_hint))
     }
 
-    fn get_hash<U>(hasher: SipHasher, key: &U) -> usize
+    /// Constructs a new `Ring<T>` with a specified hash builder, list of nodes with weights, and
+    /// capacity hint. The actual capacity of the ring will always be the next prime greater than
+    /// or equal to `capacity_hint`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty or if a weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let ring = Ring::with_hasher_and_weights(
+    ///     StableBuildHasher::with_keys(1, 2),
+    ///     vec![("node-1", 1), ("node-2", 3)],
+    ///     100,
+    /// );
+    /// assert_eq!(ring.capacity(), 101);
+    /// ```
+    pub fn with_hasher_and_weights(
+        hash_builder: H,
+        nodes: Vec<(T, u32)>,
+        capacity_hint: usize,
+    ) -> Self
     where
-        U: Hash,
+        T: Hash,
+        H: BuildHasher,
     {
-        let mut sip = hasher;
-        key.hash(&mut sip);
-        sip.finish() as usize
+        assert!(!nodes.is_empty());
+        assert!(nodes.iter().all(|node| node.1 > 0));
+        let (nodes, weights) = nodes.into_iter().unzip();
+        Self::build(hash_builder, nodes, weights, capacity_hint)
     }
 
-    fn populate(hashers: &[SipHasher; 2], nodes: &[&T], capacity_hint: usize) -> Vec<usize>
+    /// Constructs a new `Ring<T>` with a specified hash builder, list of nodes with weights, and
+    /// capacity hint.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty, `Error::InvalidWeight` if a weight is zero,
+    /// and `Error::InvalidCapacity` if `capacity_hint` is less than the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::{Error, StableBuildHasher};
+    ///
+    /// let hash_builder = StableBuildHasher::new();
+    ///
+    /// let ring = Ring::try_with_hasher_and_weights(hash_builder, vec![("node-1", 2)], 100);
+    /// assert_eq!(ring.map(|ring| ring.capacity()), Ok(101));
+    ///
+    /// let ring = Ring::try_with_hasher_and_weights(hash_builder, vec![("node-1", 0)], 100);
+    /// assert_eq!(ring.err(), Some(Error::InvalidWeight));
+    /// ```
+    pub fn try_with_hasher_and_weights(
+        hash_builder: H,
+        nodes: Vec<(T, u32)>,
+        capacity_hint: usize,
+    ) -> Result<Self, Error>
     where
-        T: 'a + Hash,
+        T: Hash,
+        H: BuildHasher,
     {
-        let m = Sieve::new(capacity_hint * 2)
-            .primes_from(capacity_hint)
-            .next()
-            .expect("Expected a prime larger than or equal to `capacity_hint`.");
-        let n = nodes.len();
+        if nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        if nodes.iter().any(|node| node.1 == 0) {
+            return Err(Error::InvalidWeight);
+        }
+        if capacity_hint < nodes.len() {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self::with_hasher_and_weights(
+            hash_builder,
+            nodes,
+            capacity_hint,
+        ))
+    }
 
-        let permutation: Vec<Vec<usize>> = nodes
+    fn build(hash_builder: H, nodes: Vec<T>, weights: Vec<u32>, capacity_hint: usize) -> Self
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        let capacity = next_prime(capacity_hint);
+        let permutations: Vec<(usize, usize)> = nodes
             .iter()
             .map(|node| {
-                let offset = Self::get_hash(hashers[0], node) % m;
-                let skip = (Self::get_hash(hashers[1], node) % (m - 1)) + 1;
-                (0..m).map(|i| (offset + i * skip) % m).collect()
+                let hash = util::gen_hash(&hash_builder, node);
+                let offset = util::combine_hash(&hash_builder, hash, 0) as usize % capacity;
+                let skip =
+                    (util::combine_hash(&hash_builder, hash, 1) as usize % (capacity - 1)) + 1;
+                (offset, skip)
             })
             .collect();
-
-        let mut next: Vec<usize> = iter::repeat(0).take(n).collect();
-        let mut entry: Vec<usize> = iter::repeat(<usize>::max_value()).take(m).collect();
-
-        let mut i = 0;
-        while i < m {
-            for j in 0..n {
-                let mut c = permutation[j][next[j]];
-                while entry[c] != <usize>::max_value() {
-                    next[j] += 1;
-                    c = permutation[j][next[j]];
-                }
-                entry[c] = j;
-                next[j] += 1;
-                i += 1;
-
-                if i == m {
-                    break;
-                }
+        // A node claims an entry in round `i` if `i * weight` has reached its target, which then
+        // increases by the largest weight, so nodes with equal weights claim entries in turn.
+        let max_weight = weights.iter().cloned().max().map_or(1, u64::from);
+        let mut targets = vec![0; weights.len()];
+        let lookup = Lookup::populate(&permutations, capacity, |node, round| {
+            if round * u64::from(weights[node]) < targets[node] {
+                return false;
             }
+            targets[node] += max_weight;
+            true
+        });
+        Self {
+            nodes,
+            weights,
+            lookup,
+            permutations,
+            hash_builder,
         }
-
-        entry
     }
 
     /// Returns the number of nodes in the ring.
@@ -139,117 +507,1488 @@ impl<'a, T> Ring<'a, T> {
     /// ```
     /// use hash_rings::maglev::Ring;
     ///
-    /// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
     /// assert_eq!(ring.nodes(), 3);
     /// ```
     pub fn nodes(&self) -> usize {
         self.nodes.len()
     }
 
+    /// Returns an iterator over the nodes in the ring and their weights. Nodes of rings that were
+    /// constructed without weights have a weight of one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::with_weights(vec![("node-1", 1), ("node-2", 3)], 100);
+    ///
+    /// let mut iterator = ring.iter_weights();
+    /// assert_eq!(iterator.next(), Some((&"node-1", 1)));
+    /// assert_eq!(iterator.next(), Some((&"node-2", 3)));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter_weights(&self) -> impl Iterator<Item = (&T, u32)> {
+        self.nodes.iter().zip(self.weights.iter().cloned())
+    }
+
     /// Returns the capacity of the ring. If nodes are removed and the ring is regenerated, the
-    /// ring should be rebuilt with the same capacity.
+    /// ring should be rebuilt with the same capacity using `rebuild`.
     ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::maglev::Ring;
     ///
-    /// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
     /// assert_eq!(ring.capacity(), 307);
     /// ```
     pub fn capacity(&self) -> usize {
         self.lookup.len()
     }
 
-    /// Returns the node associated with a key.
+    /// Constructs a new `Ring<T>` with a specified list of nodes and the capacity and hash builder
+    /// of this ring. Returns the new ring and the entries of the lookup table whose node changed.
+    ///
+    /// Since nodes claim entries in the order that they are listed, nodes that remain in the ring
+    /// should keep their relative order to minimize the disruption.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty or if `nodes` has more nodes than the capacity of the ring.
     ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::maglev::Ring;
     ///
-    /// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
-    /// assert_eq!(ring.get_node(&"point-1"), &"node-3");
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let (ring, disruption) = ring.rebuild(vec!["node-1", "node-2"]);
+    ///
+    /// assert_eq!(ring.capacity(), 307);
+    /// assert!(disruption.fraction() < 0.5);
     /// ```
-    pub fn get_node<U>(&self, key: &U) -> &T
+    pub fn rebuild(&self, nodes: Vec<T>) -> (Self, Disruption)
     where
-        U: Hash,
+        T: Hash + Eq,
+        H: BuildHasher + Clone,
     {
-        let index = Self::get_hash(self.hasher, key) % self.capacity();
-        self.nodes[self.lookup[index]]
+        match self.try_rebuild(nodes) {
+            Ok(rebuilt) => rebuilt,
+            Err(err) => panic!("Error: {}.", err),
+        }
     }
 
-    /// Returns an iterator over the ring. The iterator will yield the nodes in the ring.
+    /// Constructs a new `Ring<T>` with a specified list of nodes and the capacity and hash builder
+    /// of this ring. Returns the new ring and the entries of the lookup table whose node changed.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty and `Error::InvalidCapacity` if `nodes` has
+    /// more nodes than the capacity of the ring.
     ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::maglev::Ring;
+    /// use hash_rings::Error;
     ///
-    /// let ring = Ring::new(vec![&"node-1", &"node-2", &"node-3"]);
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
     ///
-    /// let mut iterator = ring.iter();
-    /// assert_eq!(iterator.next(), Some(&"node-1"));
-    /// assert_eq!(iterator.next(), Some(&"node-2"));
-    /// assert_eq!(iterator.next(), Some(&"node-3"));
-    /// assert_eq!(iterator.next(), None);
+    /// assert!(ring.try_rebuild(vec!["node-1", "node-2"]).is_ok());
+    /// assert_eq!(ring.try_rebuild(vec![]).err(), Some(Error::EmptyRing));
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = &'a T> {
-        self.nodes.iter().cloned()
+    pub fn try_rebuild(&self, nodes: Vec<T>) -> Result<(Self, Disruption), Error>
+    where
+        T: Hash + Eq,
+        H: BuildHasher + Clone,
+    {
+        let ring = Self::try_with_hasher(self.hash_builder.clone(), nodes, self.capacity())?;
+        let disruption = self.get_disruption(&ring);
+        Ok((ring, disruption))
     }
-}
-
-impl<'a, T> IntoIterator for &'a Ring<'a, T>
-where
-    T: Hash + Eq,
-{
-    type IntoIter = Box<dyn Iterator<Item = &'a T> + 'a>;
-    type Item = (&'a T);
 
-    fn into_iter(self) -> Self::IntoIter {
-        Box::new(self.iter())
+    /// Constructs a new `Ring<T>` with a specified list of nodes with weights and the capacity and
+    /// hash builder of this ring. Returns the new ring and the entries of the lookup table whose
+    /// node changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty, if a weight is zero, or if `nodes` has more nodes than the
+    /// capacity of the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::with_weights(vec![("node-1", 1), ("node-2", 1)], 100);
+    /// let (ring, disruption) = ring.rebuild_with_weights(vec![("node-1", 1), ("node-2", 2)]);
+    ///
+    /// assert_eq!(ring.capacity(), 101);
+    /// assert!(disruption.fraction() > 0.0);
+    /// ```
+    pub fn rebuild_with_weights(&self, nodes: Vec<(T, u32)>) -> (Self, Disruption)
+    where
+        T: Hash + Eq,
+        H: BuildHasher + Clone,
+    {
+        match self.try_rebuild_with_weights(nodes) {
+            Ok(rebuilt) => rebuilt,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new `Ring<T>` with a specified list of nodes with weights and the capacity and
+    /// hash builder of this ring. Returns the new ring and the entries of the lookup table whose
+    /// node changed.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty, `Error::InvalidWeight` if a weight is zero,
+    /// and `Error::InvalidCapacity` if `nodes` has more nodes than the capacity of the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2"]);
+    ///
+    /// assert!(ring.try_rebuild_with_weights(vec![("node-1", 2)]).is_ok());
+    /// assert_eq!(
+    ///     ring.try_rebuild_with_weights(vec![("node-1", 0)]).err(),
+    ///     Some(Error::InvalidWeight),
+    /// );
+    /// ```
+    pub fn try_rebuild_with_weights(
+        &self,
+        nodes: Vec<(T, u32)>,
+    ) -> Result<(Self, Disruption), Error>
+    where
+        T: Hash + Eq,
+        H: BuildHasher + Clone,
+    {
+        let ring =
+            Self::try_with_hasher_and_weights(self.hash_builder.clone(), nodes, self.capacity())?;
+        let disruption = self.get_disruption(&ring);
+        Ok((ring, disruption))
+    }
+
+    fn get_disruption(&self, other: &Self) -> Disruption
+    where
+        T: Eq,
+    {
+        let slots = self
+            .lookup
+            .iter()
+            .zip(other.lookup.iter())
+            .enumerate()
+            .filter(|(_, (node, other_node))| self.nodes[*node] != other.nodes[*other_node])
+            .map(|(slot, _)| slot)
+            .collect();
+        Disruption {
+            slots,
+            capacity: self.capacity(),
+        }
+    }
+
+    /// Returns the node associated with a key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// assert_eq!(ring.get_node(&"point-1"), &"node-3");
+    /// ```
+    pub fn get_node<U>(&self, key: &U) -> &T
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_node_by_hash(util::gen_hash(&self.hash_builder, key))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a key in order of preference.
+    ///
+    /// The first node is the node returned by `get_node`. The remaining nodes are ordered by how
+    /// early the key's entry in the lookup table appears in their permutations, which is the order
+    /// in which they would have claimed the entry. Ties are broken by the order of the nodes. Fewer
+    /// than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// The order only depends on the nodes, the capacity, and the hash builder, so the remaining
+    /// nodes can be used as deterministic fallbacks when the first node is unavailable without
+    /// rebuilding the lookup table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 4);
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    ///
+    /// // Retry on the next node in order of preference if "node-3" is unavailable.
+    /// let node = ring
+    ///     .get_nodes(&"point-1", 3)
+    ///     .into_iter()
+    ///     .find(|node| **node != "node-3");
+    /// assert!(node.is_some());
+    /// ```
+    pub fn get_nodes<U>(&self, key: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, key), n)
+    }
+
+    /// Returns the node associated with a hash of a key. The hash of a key is the value returned by
+    /// `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same node as
+    /// `get_node` for the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T {
+        &self.nodes[self.lookup.get(hash as usize % self.capacity())]
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a key in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        let m = self.capacity();
+        let index = hash as usize % m;
+        let owner = self.lookup.get(index);
+        let mut preferences: Vec<(usize, usize)> = self
+            .permutations
+            .iter()
+            .enumerate()
+            .filter(|&(node, _)| node != owner)
+            .map(|(node, &(offset, skip))| (Self::get_position(index, offset, skip, m), node))
+            .collect();
+        preferences.sort();
+
+        iter::once(owner)
+            .chain(preferences.into_iter().map(|preference| preference.1))
+            .take(n)
+            .map(|node| &self.nodes[node])
+            .collect()
+    }
+
+    // Returns `i` such that `(offset + i * skip) % m == index`. Since `m` is prime, the inverse of
+    // `skip` is `skip ^ (m - 2)`.
+    fn get_position(index: usize, offset: usize, skip: usize, m: usize) -> usize {
+        let mul_mod = |a: usize, b: usize| (a as u128 * b as u128 % m as u128) as usize;
+        let mut inverse = 1;
+        let mut base = skip % m;
+        let mut exponent = m - 2;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                inverse = mul_mod(inverse, base);
+            }
+            base = mul_mod(base, base);
+            exponent >>= 1;
+        }
+        mul_mod((index + m - offset) % m, inverse)
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield the nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    ///
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some(&"node-1"));
+    /// assert_eq!(iterator.next(), Some(&"node-2"));
+    /// assert_eq!(iterator.next(), Some(&"node-3"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes.iter()
+    }
+}
+
+/// The entries of a maglev lookup table whose node changed when the ring was rebuilt.
+///
+/// Since keys are mapped to entries uniformly, the fraction of entries that changed is the
+/// expected fraction of keys that are mapped to a different node.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::maglev::Ring;
+///
+/// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+/// let (other, disruption) = ring.rebuild(vec!["node-1", "node-2"]);
+///
+/// assert_eq!(disruption.capacity(), 307);
+/// for &slot in disruption.slots() {
+///     assert!(slot < disruption.capacity());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disruption {
+    slots: Vec<usize>,
+    capacity: usize,
+}
+
+impl Disruption {
+    /// Returns the indices of the entries of the lookup table whose node changed in increasing
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let (_, disruption) = ring.rebuild(vec!["node-1", "node-2", "node-3"]);
+    ///
+    /// assert!(disruption.slots().is_empty());
+    /// ```
+    pub fn slots(&self) -> &[usize] {
+        &self.slots
+    }
+
+    /// Returns the capacity of the lookup table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let (_, disruption) = ring.rebuild(vec!["node-1", "node-2"]);
+    ///
+    /// assert_eq!(disruption.capacity(), ring.capacity());
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the fraction of the entries of the lookup table whose node changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let (_, disruption) = ring.rebuild(vec!["node-1", "node-2"]);
+    ///
+    /// assert!(disruption.fraction() > 0.3 && disruption.fraction() < 0.5);
+    /// ```
+    pub fn fraction(&self) -> f64 {
+        self.slots.len() as f64 / self.capacity as f64
+    }
+}
+
+impl<'a, T, H> IntoIterator for &'a Ring<T, H>
+where
+    T: Hash + Eq,
+{
+    type IntoIter = Box<dyn Iterator<Item = &'a T> + 'a>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Serializes the ids and weights of the nodes and the capacity of the lookup table. The hash
+/// builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for Ring<T, H>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            capacity: usize,
+            nodes: &'a [T],
+            weights: &'a [u32],
+        }
+
+        State {
+            capacity: self.capacity(),
+            nodes: &self.nodes,
+            weights: &self.weights,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and weights of its nodes and the capacity of its lookup table.
+/// Nodes have a weight of one if the weights are missing. The ring is rebuilt with `H::default()`,
+/// so lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for Ring<T, H>
+where
+    T: Deserialize<'de> + Hash,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            capacity: usize,
+            nodes: Vec<T>,
+            #[serde(default)]
+            weights: Option<Vec<u32>>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let weights = match state.weights {
+            Some(weights) if weights.len() != state.nodes.len() => {
+                return Err(de::Error::custom(Error::InvalidWeight));
+            }
+            Some(weights) => weights,
+            None => vec![1; state.nodes.len()],
+        };
+        let nodes = state.nodes.into_iter().zip(weights).collect();
+        Self::try_with_hasher_and_weights(H::default(), nodes, state.capacity)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The key of a flow, which consists of the source and destination addresses and ports of a
+/// connection and its protocol.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::maglev::FlowKey;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let key = FlowKey {
+///     src_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+///     src_port: 49152,
+///     dst_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 1, 1)),
+///     dst_port: 80,
+///     protocol: 6,
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    /// The source address of the flow.
+    pub src_addr: IpAddr,
+    /// The source port of the flow.
+    pub src_port: u16,
+    /// The destination address of the flow.
+    pub dst_addr: IpAddr,
+    /// The destination port of the flow.
+    pub dst_port: u16,
+    /// The IP protocol number of the flow.
+    pub protocol: u8,
+}
+
+struct Flow<T> {
+    node: T,
+    last_seen: Instant,
+    stamp: u64,
+}
+
+/// A connection tracking table that pins flows to the nodes of a maglev ring.
+///
+/// As described in the Maglev paper, the node of a flow is looked up in the ring only when the
+/// flow is first seen. Later packets of the flow are sent to the same node even if the ring is
+/// replaced by a rebuilt ring that maps the flow elsewhere, unless the node was removed from the
+/// ring.
+///
+/// The table holds at most `capacity` flows. Flows that have not been seen for `idle_timeout` are
+/// evicted, and the least recently seen flow is evicted when a new flow would exceed the
+/// capacity.
+///
+/// # Examples
+/// ```
+/// use hash_rings::maglev::{FlowTracker, Ring};
+/// use std::time::Duration;
+///
+/// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+/// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+///
+/// let node = *tracker.get_node(&"flow-1");
+///
+/// // Established flows keep their node when the ring is rebuilt with more nodes.
+/// let (ring, _) = tracker.ring().rebuild(vec!["node-1", "node-2", "node-3", "node-4"]);
+/// tracker.set_ring(ring);
+/// assert_eq!(tracker.get_node(&"flow-1"), &node);
+/// ```
+pub struct FlowTracker<T, K = FlowKey, H = StableBuildHasher> {
+    ring: Ring<T, H>,
+    capacity: usize,
+    idle_timeout: Duration,
+    flows: HashMap<K, Flow<T>>,
+    order: BTreeMap<u64, K>,
+    next_stamp: u64,
+}
+
+impl<T, K, H> FlowTracker<T, K, H> {
+    /// Constructs a new, empty `FlowTracker<T, K, H>` that looks up new flows in `ring`, holds at
+    /// most `capacity` flows, and evicts flows that have not been seen for `idle_timeout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: FlowTracker<_, &str> = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// ```
+    pub fn new(ring: Ring<T, H>, capacity: usize, idle_timeout: Duration) -> Self
+    where
+        K: Hash + Eq,
+    {
+        match Self::try_new(ring, capacity, idle_timeout) {
+            Ok(tracker) => tracker,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new, empty `FlowTracker<T, K, H>` that looks up new flows in `ring`, holds at
+    /// most `capacity` flows, and evicts flows that have not been seen for `idle_timeout`.
+    ///
+    /// Returns `Error::InvalidCapacity` if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use hash_rings::Error;
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: Result<FlowTracker<_, &str>, _> =
+    ///     FlowTracker::try_new(ring, 0, Duration::from_secs(60));
+    /// assert_eq!(tracker.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_new(ring: Ring<T, H>, capacity: usize, idle_timeout: Duration) -> Result<Self, Error>
+    where
+        K: Hash + Eq,
+    {
+        if capacity == 0 {
+            return Err(Error::InvalidCapacity);
+        }
+
+        Ok(Self {
+            ring,
+            capacity,
+            idle_timeout,
+            flows: HashMap::new(),
+            order: BTreeMap::new(),
+            next_stamp: 0,
+        })
+    }
+
+    /// Returns the ring that new flows are looked up in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: FlowTracker<_, &str> = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// assert_eq!(tracker.ring().nodes(), 3);
+    /// ```
+    pub fn ring(&self) -> &Ring<T, H> {
+        &self.ring
+    }
+
+    /// Replaces the ring that new flows are looked up in and returns the previous ring. Flows
+    /// whose node is not in the new ring are removed so that they are looked up again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// tracker.get_node(&"flow-1");
+    ///
+    /// let previous = tracker.set_ring(Ring::new(vec!["node-4"]));
+    /// assert_eq!(previous.nodes(), 3);
+    /// assert!(tracker.is_empty());
+    /// assert_eq!(tracker.get_node(&"flow-1"), &"node-4");
+    /// ```
+    pub fn set_ring(&mut self, ring: Ring<T, H>) -> Ring<T, H>
+    where
+        T: Hash + Eq,
+        K: Hash + Eq,
+    {
+        let previous = mem::replace(&mut self.ring, ring);
+        let nodes: HashSet<&T> = self.ring.iter().collect();
+        let flows = &mut self.flows;
+        self.order.retain(|_, key| {
+            let retained = nodes.contains(&flows[key].node);
+            if !retained {
+                flows.remove(key);
+            }
+            retained
+        });
+        previous
+    }
+
+    /// Returns the maximum number of flows in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: FlowTracker<_, &str> = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// assert_eq!(tracker.capacity(), 1024);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the duration after which a flow that has not been seen is evicted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: FlowTracker<_, &str> = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// assert_eq!(tracker.idle_timeout(), Duration::from_secs(60));
+    /// ```
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Returns the number of flows in the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    ///
+    /// tracker.get_node(&"flow-1");
+    /// assert_eq!(tracker.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.flows.len()
+    }
+
+    /// Returns `true` if the table has no flows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let tracker: FlowTracker<_, &str> = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    /// assert!(tracker.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+
+    /// Returns the node associated with a flow. If the flow is not in the table, it is looked up
+    /// in the ring and inserted into the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    ///
+    /// let node = *tracker.ring().get_node(&"flow-1");
+    /// assert_eq!(tracker.get_node(&"flow-1"), &node);
+    /// ```
+    pub fn get_node(&mut self, key: &K) -> &T
+    where
+        T: Clone,
+        K: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        self.get_node_at(key, Instant::now())
+    }
+
+    /// Returns the node associated with a flow at time `now`. If the flow is not in the table, it
+    /// is looked up in the ring and inserted into the table. Flows that have been idle at `now`
+    /// are evicted first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    ///
+    /// let now = Instant::now();
+    /// tracker.get_node_at(&"flow-1", now);
+    /// tracker.get_node_at(&"flow-2", now + Duration::from_secs(90));
+    /// assert_eq!(tracker.len(), 1);
+    /// ```
+    pub fn get_node_at(&mut self, key: &K, now: Instant) -> &T
+    where
+        T: Clone,
+        K: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        self.evict_idle(now);
+
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        if let Some(flow) = self.flows.get_mut(key) {
+            let key = self
+                .order
+                .remove(&flow.stamp)
+                .expect("Expected flow to be ordered.");
+            self.order.insert(stamp, key);
+            flow.last_seen = now;
+            flow.stamp = stamp;
+        } else {
+            if self.flows.len() == self.capacity {
+                self.evict_least_recent();
+            }
+            let flow = Flow {
+                node: self.ring.get_node(key).clone(),
+                last_seen: now,
+                stamp,
+            };
+            self.order.insert(stamp, key.clone());
+            self.flows.insert(key.clone(), flow);
+        }
+        &self.flows[key].node
+    }
+
+    /// Removes a flow from the table and returns its node, or `None` if the flow is not in the
+    /// table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::Duration;
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    ///
+    /// let node = *tracker.get_node(&"flow-1");
+    /// assert_eq!(tracker.remove_flow(&"flow-1"), Some(node));
+    /// assert_eq!(tracker.remove_flow(&"flow-1"), None);
+    /// ```
+    pub fn remove_flow(&mut self, key: &K) -> Option<T>
+    where
+        K: Hash + Eq,
+    {
+        let flow = self.flows.remove(key)?;
+        self.order.remove(&flow.stamp);
+        Some(flow.node)
+    }
+
+    /// Evicts the flows that have not been seen for the idle timeout at time `now` and returns the
+    /// number of evicted flows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::{FlowTracker, Ring};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let ring = Ring::new(vec!["node-1", "node-2", "node-3"]);
+    /// let mut tracker = FlowTracker::new(ring, 1024, Duration::from_secs(60));
+    ///
+    /// let now = Instant::now();
+    /// tracker.get_node_at(&"flow-1", now);
+    /// assert_eq!(tracker.evict_idle(now + Duration::from_secs(30)), 0);
+    /// assert_eq!(tracker.evict_idle(now + Duration::from_secs(60)), 1);
+    /// ```
+    pub fn evict_idle(&mut self, now: Instant) -> usize
+    where
+        K: Hash + Eq,
+    {
+        // Flows are ordered by when they were last seen, so the idle flows are the oldest ones.
+        let mut evicted = 0;
+        while let Some((_, key)) = self.order.iter().next() {
+            let last_seen = self.flows[key].last_seen;
+            if now.saturating_duration_since(last_seen) < self.idle_timeout {
+                break;
+            }
+            self.evict_least_recent();
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn evict_least_recent(&mut self)
+    where
+        K: Hash + Eq,
+    {
+        let stamp = *self.order.keys().next().expect("Expected a flow to evict.");
+        let key = self
+            .order
+            .remove(&stamp)
+            .expect("Expected flow to be ordered.");
+        self.flows.remove(&key);
+    }
+}
+
+fn xxhash64(bytes: &[u8], seed: u64) -> u64 {
+    let mut hasher = XxHash64::with_seed(seed);
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// A maglev ring that produces the same lookup table as Envoy's maglev load balancer.
+///
+/// Each node is identified by the string that Envoy hashes for the host, which is its address such
+/// as `"10.0.0.1:80"`, or its hostname if `use_hostname_for_hashing` is enabled. The offset and skip
+/// of a node are derived from the xxHash64 of that string with seeds zero and one, and nodes claim
+/// entries in proportion to their weights using Envoy's floating point arithmetic. Nodes must be
+/// listed in the same order as the hosts of the Envoy cluster to produce the same lookup table.
+///
+/// Since Envoy computes the hashes of requests with its hash policies, `get_node_by_hash` accepts
+/// such a hash directly. `get_node` hashes the bytes of a key with xxHash64, which is how Envoy
+/// hashes header values.
+///
+/// # Examples
+/// ```
+/// use hash_rings::maglev::EnvoyRing;
+///
+/// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 1), ("10.0.0.3:80", 2)]);
+///
+/// assert_eq!(ring.capacity(), 65537);
+/// assert_eq!(ring.get_node(&"user-1"), ring.get_node_by_hash(0xA173_746B_114C_6BE8));
+/// ```
+#[derive(Clone)]
+pub struct EnvoyRing<T> {
+    nodes: Vec<T>,
+    weights: Vec<u32>,
+    lookup: Lookup,
+}
+
+impl<T> EnvoyRing<T> {
+    /// Constructs a new `EnvoyRing<T>` with a specified list of nodes with weights and Envoy's
+    /// default table size of 65537.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty or if a weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 3)]);
+    /// ```
+    pub fn new(nodes: Vec<(T, u32)>) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::with_table_size(nodes, ENVOY_TABLE_SIZE)
+    }
+
+    /// Constructs a new `EnvoyRing<T>` with a specified list of nodes with weights and Envoy's
+    /// default table size of 65537.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty and `Error::InvalidWeight` if a weight is
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(EnvoyRing::try_new(vec![("10.0.0.1:80", 1)]).is_ok());
+    /// assert_eq!(
+    ///     EnvoyRing::try_new(vec![("10.0.0.1:80", 0)]).err(),
+    ///     Some(Error::InvalidWeight),
+    /// );
+    /// ```
+    pub fn try_new(nodes: Vec<(T, u32)>) -> Result<Self, Error>
+    where
+        T: AsRef<str>,
+    {
+        Self::try_with_table_size(nodes, ENVOY_TABLE_SIZE)
+    }
+
+    /// Constructs a new `EnvoyRing<T>` with a specified list of nodes with weights and table size,
+    /// which corresponds to the `table_size` of Envoy's maglev load balancer configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty, if a weight is zero, or if `table_size` is not a prime that is
+    /// greater than or equal to the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::with_table_size(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 3)], 7);
+    /// assert_eq!(ring.capacity(), 7);
+    /// ```
+    pub fn with_table_size(nodes: Vec<(T, u32)>, table_size: usize) -> Self
+    where
+        T: AsRef<str>,
+    {
+        match Self::try_with_table_size(nodes, table_size) {
+            Ok(ring) => ring,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new `EnvoyRing<T>` with a specified list of nodes with weights and table size,
+    /// which corresponds to the `table_size` of Envoy's maglev load balancer configuration.
+    ///
+    /// Returns `Error::EmptyRing` if `nodes` is empty, `Error::InvalidWeight` if a weight is zero,
+    /// and `Error::InvalidCapacity` if `table_size` is not a prime that is greater than or equal to
+    /// the number of nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    /// use hash_rings::Error;
+    ///
+    /// let ring = EnvoyRing::try_with_table_size(vec![("10.0.0.1:80", 1)], 7);
+    /// assert_eq!(ring.map(|ring| ring.capacity()), Ok(7));
+    ///
+    /// let ring = EnvoyRing::try_with_table_size(vec![("10.0.0.1:80", 1)], 8);
+    /// assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_table_size(nodes: Vec<(T, u32)>, table_size: usize) -> Result<Self, Error>
+    where
+        T: AsRef<str>,
+    {
+        if nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        if nodes.iter().any(|node| node.1 == 0) {
+            return Err(Error::InvalidWeight);
+        }
+        if table_size < nodes.len() || !primal::is_prime(table_size as u64) {
+            return Err(Error::InvalidCapacity);
+        }
+
+        let m = table_size as u64;
+        let permutations: Vec<(usize, usize)> = nodes
+            .iter()
+            .map(|node| {
+                let bytes = node.0.as_ref().as_bytes();
+                let offset = xxhash64(bytes, 0) % m;
+                let skip = xxhash64(bytes, 1) % (m - 1) + 1;
+                (offset as usize, skip as usize)
+            })
+            .collect();
+
+        // Envoy normalizes the weights by their sum and claims an entry for a node in round `i` if
+        // `i * weight` is not less than its target, which then increases by the largest weight.
+        let (nodes, weights): (Vec<T>, Vec<u32>) = nodes.into_iter().unzip();
+        let total_weight = weights.iter().map(|weight| u64::from(*weight)).sum::<u64>() as f64;
+        let normalized_weights: Vec<f64> = weights
+            .iter()
+            .map(|weight| f64::from(*weight) / total_weight)
+            .collect();
+        let max_weight = normalized_weights.iter().cloned().fold(0f64, f64::max);
+        let mut targets = vec![0f64; nodes.len()];
+        let lookup = Lookup::populate(&permutations, table_size, |node, round| {
+            if round as f64 * normalized_weights[node] < targets[node] {
+                return false;
+            }
+            targets[node] += max_weight;
+            true
+        });
+
+        Ok(Self {
+            nodes,
+            weights,
+            lookup,
+        })
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 3)]);
+    /// assert_eq!(ring.nodes(), 2);
+    /// ```
+    pub fn nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the size of the lookup table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 3)]);
+    /// assert_eq!(ring.capacity(), 65537);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.lookup.len()
+    }
+
+    /// Returns the node associated with a key. The key is hashed with xxHash64 with a seed of
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1)]);
+    /// assert_eq!(ring.get_node(&"user-1"), &"10.0.0.1:80");
+    /// ```
+    pub fn get_node<U>(&self, key: &U) -> &T
+    where
+        U: AsRef<[u8]> + ?Sized,
+    {
+        self.get_node_by_hash(xxhash64(key.as_ref(), 0))
+    }
+
+    /// Returns the node associated with a hash computed by an Envoy hash policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1)]);
+    /// assert_eq!(ring.get_node_by_hash(42), &"10.0.0.1:80");
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T {
+        &self.nodes[self.lookup.get((hash % self.capacity() as u64) as usize)]
+    }
+
+    /// Returns an iterator over the nodes in the ring and their weights.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::EnvoyRing;
+    ///
+    /// let ring = EnvoyRing::new(vec![("10.0.0.1:80", 1), ("10.0.0.2:80", 3)]);
+    ///
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some((&"10.0.0.1:80", 1)));
+    /// assert_eq!(iterator.next(), Some((&"10.0.0.2:80", 3)));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u32)> {
+        self.nodes.iter().zip(self.weights.iter().cloned())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a EnvoyRing<T> {
+    type IntoIter = Box<dyn Iterator<Item = (&'a T, u32)> + 'a>;
+    type Item = (&'a T, u32);
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Serializes the ids and weights of the nodes and the size of the lookup table.
+#[cfg(feature = "serde")]
+impl<T> Serialize for EnvoyRing<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            capacity: usize,
+            nodes: Vec<(&'a T, u32)>,
+        }
+
+        State {
+            capacity: self.capacity(),
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and weights of its nodes and the size of its lookup table.
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for EnvoyRing<T>
+where
+    T: Deserialize<'de> + AsRef<str>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            capacity: usize,
+            nodes: Vec<(T, u32)>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        Self::try_with_table_size(state.nodes, state.capacity).map_err(de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Ring;
+    use super::{EnvoyRing, FlowKey, FlowTracker, Lookup, Ring};
+    use crate::util;
+    use crate::{Error, StableBuildHasher};
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::{Duration, Instant};
 
     #[test]
     #[should_panic]
     fn test_new_empty() {
-        let _ring: Ring<'_, u32> = Ring::new(vec![]);
+        let _ring: Ring<u32> = Ring::new(vec![]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_capacity_hint_empty() {
+        let _ring: Ring<u32> = Ring::with_capacity_hint(vec![], 100);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_hasher_empty() {
+        let _ring: Ring<u32, _> = Ring::with_hasher(StableBuildHasher::new(), vec![], 100);
+    }
+
+    #[test]
+    fn test_try_new_empty() {
+        let ring: Result<Ring<u32>, Error> = Ring::try_new(vec![]);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+
+        let ring: Result<Ring<u32>, Error> = Ring::try_with_capacity_hint(vec![], 100);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+    }
+
+    #[test]
+    fn test_try_with_capacity_hint_too_small() {
+        let ring = Ring::try_with_capacity_hint(vec![0, 1, 2], 2);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+
+        let ring = Ring::try_with_capacity_hint(vec![0, 1, 2], 3);
+        assert_eq!(ring.map(|ring| ring.capacity()), Ok(3));
     }
 
     #[test]
     fn test_get_node() {
-        let ring = Ring::new(vec![&0, &1, &2]);
-        assert_eq!(ring.get_node(&0), &0);
-        assert_eq!(ring.get_node(&1), &1);
+        let ring = Ring::new(vec![0, 1, 2]);
+        assert_eq!(ring.get_node(&0), &2);
+        assert_eq!(ring.get_node(&1), &2);
+
+        let ring = Ring::with_capacity_hint(vec![0, 1], ring.capacity());
+        assert_eq!(ring.get_node(&0), &1);
+        assert_eq!(ring.get_node(&1), &0);
+    }
+
+    #[test]
+    fn test_with_hasher() {
+        let nodes: Vec<u32> = (0..10).collect();
+        let ring = Ring::with_hasher(StableBuildHasher::new(), nodes.clone(), 1000);
+        let same = Ring::with_capacity_hint(nodes.clone(), 1000);
+        let other = Ring::with_hasher(StableBuildHasher::with_keys(1, 2), nodes, 1000);
+
+        assert_eq!(ring.lookup, same.lookup);
+        assert_ne!(ring.permutations, other.permutations);
+        assert_ne!(ring.lookup, other.lookup);
+    }
+
+    #[test]
+    fn test_try_with_hasher() {
+        let ring = Ring::<u32, _>::try_with_hasher(StableBuildHasher::new(), vec![], 100);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+
+        let ring = Ring::try_with_hasher(StableBuildHasher::new(), vec![0, 1, 2], 2);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(super::next_prime(0), 2);
+        assert_eq!(super::next_prime(1000), 1009);
+        assert_eq!(super::next_prime(65537), 65537);
+    }
+
+    #[test]
+    fn test_lookup_entry_size() {
+        let ring = Ring::with_capacity_hint((0..100).collect(), 1000);
+        assert!(match ring.lookup {
+            Lookup::Small(_) => true,
+            Lookup::Large(_) => false,
+        });
+
+        let ring = Ring::with_capacity_hint((0..70000).collect(), 70000);
+        assert!(match ring.lookup {
+            Lookup::Small(_) => false,
+            Lookup::Large(_) => true,
+        });
+        let mut entries = vec![0; ring.nodes()];
+        for node in ring.lookup.iter() {
+            entries[node] += 1;
+        }
+        assert!(entries.iter().all(|count| *count >= 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_weights_zero_weight() {
+        let _ring = Ring::with_weights(vec![(0, 1), (1, 0)], 100);
+    }
+
+    #[test]
+    fn test_try_with_weights() {
+        let ring: Result<Ring<u32>, Error> = Ring::try_with_weights(vec![], 100);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+
+        let ring = Ring::try_with_weights(vec![(0, 1), (1, 0)], 100);
+        assert_eq!(ring.err(), Some(Error::InvalidWeight));
+
+        let ring = Ring::try_with_weights(vec![(0, 1), (1, 2), (2, 3)], 2);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    }
+
+    #[test]
+    fn test_with_weights_equal_weights() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 1000);
+        let other = Ring::with_weights(vec![(0, 5), (1, 5), (2, 5)], 1000);
+        assert_eq!(ring.lookup, other.lookup);
+    }
 
-        let ring = Ring::with_capacity_hint(vec![&0, &1], ring.capacity());
-        assert_eq!(ring.get_node(&0), &0);
-        assert_eq!(ring.get_node(&1), &1);
+    #[test]
+    fn test_with_weights_entries() {
+        let weights = [1, 2, 3, 4];
+        let nodes = weights.iter().cloned().enumerate().collect();
+        let ring = Ring::with_weights(nodes, 10_000);
+        let total_weight: u32 = weights.iter().sum();
+
+        let mut entries = [0; 4];
+        for node in ring.lookup.iter() {
+            entries[node] += 1;
+        }
+        for (node, weight) in weights.iter().enumerate() {
+            let expected = ring.capacity() as f64 * f64::from(*weight) / f64::from(total_weight);
+            assert!((entries[node] as f64 - expected).abs() <= 1f64);
+        }
+    }
+
+    #[test]
+    fn test_with_weights_distribution() {
+        let ring = Ring::with_weights(vec![(0, 1), (1, 3)], 1000);
+
+        let mut counts = [0; 2];
+        for point in 0..100_000 {
+            counts[*ring.get_node(&point)] += 1;
+        }
+        let ratio = f64::from(counts[1]) / f64::from(counts[0]);
+        assert!((ratio - 3f64).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_iter_weights() {
+        let ring = Ring::with_weights(vec![(0, 1), (1, 3)], 100);
+        assert_eq!(ring.iter_weights().collect::<Vec<_>>(), [(&0, 1), (&1, 3)]);
+
+        let ring = Ring::new(vec![0, 1]);
+        assert_eq!(ring.iter_weights().collect::<Vec<_>>(), [(&0, 1), (&1, 1)]);
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2, 3], 1000);
+        let (other, disruption) = ring.rebuild(vec![0, 1, 2]);
+
+        assert_eq!(other.capacity(), ring.capacity());
+        assert_eq!(disruption.capacity(), ring.capacity());
+        let slots: Vec<usize> = (0..ring.capacity())
+            .filter(|slot| ring.lookup.get(*slot) != other.lookup.get(*slot))
+            .collect();
+        assert_eq!(disruption.slots(), &slots[..]);
+
+        // Entries of the removed node must move, and few entries of other nodes should move.
+        let removed = ring.lookup.iter().filter(|node| *node == 3).count();
+        assert!(slots.len() >= removed);
+        assert!(disruption.fraction() < 0.35);
+    }
+
+    #[test]
+    fn test_rebuild_same_nodes() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 1000);
+        let (other, disruption) = ring.rebuild(vec![0, 1, 2]);
+
+        assert_eq!(disruption.fraction(), 0f64);
+        for key in 0..100 {
+            assert_eq!(ring.get_node(&key), other.get_node(&key));
+        }
+    }
+
+    #[test]
+    fn test_rebuild_with_weights() {
+        let ring = Ring::with_capacity_hint(vec![0, 1], 1000);
+        let (other, disruption) = ring.rebuild_with_weights(vec![(0, 1), (1, 3)]);
+        let moved = disruption
+            .slots()
+            .iter()
+            .filter(|slot| ring.lookup.get(**slot) == 0 && other.lookup.get(**slot) == 1)
+            .count();
+        assert_eq!(moved, disruption.slots().len());
+        assert!((disruption.fraction() - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_try_rebuild() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 3);
+        assert_eq!(ring.try_rebuild(vec![]).err(), Some(Error::EmptyRing));
+        assert_eq!(
+            ring.try_rebuild(vec![0, 1, 2, 3]).err(),
+            Some(Error::InvalidCapacity)
+        );
+        assert_eq!(
+            ring.try_rebuild_with_weights(vec![(0, 0)]).err(),
+            Some(Error::InvalidWeight)
+        );
+    }
+
+    #[test]
+    fn test_get_position() {
+        let m = 307;
+        for &(offset, skip) in &[(0, 1), (5, 17), (306, 306), (123, 45)] {
+            for index in 0..m {
+                let position = Ring::<u32>::get_position(index, offset, skip, m);
+                assert_eq!((offset + position * skip) % m, index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_nodes() {
+        let ring = Ring::new(vec![0, 1, 2]);
+        for key in 0..100 {
+            let mut nodes = ring.get_nodes(&key, 3);
+            assert_eq!(nodes[0], ring.get_node(&key));
+            nodes.sort();
+            assert_eq!(nodes, [&0, &1, &2]);
+        }
+    }
+
+    #[test]
+    fn test_get_nodes_permutation_order() {
+        let ring = Ring::with_capacity_hint((0..5).collect(), 101);
+        for key in 0..100u32 {
+            let index = util::gen_hash(&ring.hash_builder, &key) as usize % ring.capacity();
+            let owner = ring.lookup.get(index);
+
+            // Walk every permutation until it reaches the key's entry.
+            let mut expected: Vec<(usize, usize)> = ring
+                .permutations
+                .iter()
+                .enumerate()
+                .filter(|&(node, _)| node != owner)
+                .map(|(node, &(offset, skip))| {
+                    let position = (0..ring.capacity())
+                        .position(|i| (offset + i * skip) % ring.capacity() == index)
+                        .unwrap();
+                    (position, node)
+                })
+                .collect();
+            expected.sort();
+
+            let actual = ring.get_nodes(&key, 5);
+            assert_eq!(actual[0], &owner);
+            assert_eq!(
+                actual[1..],
+                expected.iter().map(|entry| &entry.1).collect::<Vec<_>>()[..]
+            );
+            assert_eq!(ring.get_nodes(&key, 2), actual[..2]);
+        }
     }
 
     #[test]
     fn test_nodes() {
-        let ring = Ring::new(vec![&0, &1, &2]);
+        let ring = Ring::new(vec![0, 1, 2]);
         assert_eq!(ring.nodes(), 3);
     }
 
     #[test]
     fn test_capacity() {
-        let ring = Ring::new(vec![&0, &1, &2]);
+        let ring = Ring::new(vec![0, 1, 2]);
         assert_eq!(ring.capacity(), 307);
 
-        let ring = Ring::with_capacity_hint(vec![&0, &1], ring.capacity());
+        let ring = Ring::with_capacity_hint(vec![0, 1], ring.capacity());
         assert_eq!(ring.capacity(), 307);
     }
 
     #[test]
     fn test_iter() {
-        let ring = Ring::new(vec![&0, &1, &2]);
+        let ring = Ring::new(vec![0, 1, 2]);
 
         let mut iterator = ring.iter();
         assert_eq!(iterator.next(), Some(&0));
@@ -257,4 +1996,261 @@ mod tests {
         assert_eq!(iterator.next(), Some(&2));
         assert_eq!(iterator.next(), None);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 1000);
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.capacity(), ring.capacity());
+        assert_eq!(other.iter().collect::<Vec<_>>(), [&0, &1, &2]);
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+
+        let json = r#"{"capacity":307,"nodes":[]}"#;
+        assert!(serde_json::from_str::<Ring<u32>>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_weights() {
+        let ring = Ring::with_weights(vec![(0, 1), (1, 2), (2, 3)], 1000);
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.lookup, ring.lookup);
+        assert_eq!(other.weights, ring.weights);
+
+        let json = r#"{"capacity":307,"nodes":[0,1]}"#;
+        let other: Ring<u32> = serde_json::from_str(json).unwrap();
+        assert_eq!(other.weights, [1, 1]);
+
+        let json = r#"{"capacity":307,"nodes":[0,1],"weights":[1]}"#;
+        assert!(serde_json::from_str::<Ring<u32>>(json).is_err());
+
+        let json = r#"{"capacity":307,"nodes":[0,1],"weights":[1,0]}"#;
+        assert!(serde_json::from_str::<Ring<u32>>(json).is_err());
+    }
+
+    #[test]
+    fn test_envoy_xxhash64() {
+        // Test vectors from the reference implementation of xxHash.
+        assert_eq!(super::xxhash64(b"", 0), 0xEF46_DB37_51D8_E999);
+        assert_eq!(super::xxhash64(b"a", 0), 0xD24E_C4F1_A98C_6E5B);
+        assert_eq!(super::xxhash64(b"abc", 0), 0x44BC_2CF5_AD77_0999);
+    }
+
+    #[test]
+    fn test_envoy_table() {
+        // `MaglevLoadBalancerTest.Basic` in Envoy's `maglev_lb_test.cc`.
+        let nodes = (0..6).map(|i| (format!("127.0.0.1:9{}", i), 1)).collect();
+        let ring = EnvoyRing::with_table_size(nodes, 7);
+        assert_eq!(ring.lookup, Lookup::Small(vec![2, 4, 0, 1, 5, 0, 3]));
+    }
+
+    #[test]
+    fn test_envoy_weighted_table() {
+        // `MaglevLoadBalancerTest.Weighted` in Envoy's `maglev_lb_test.cc`, which also looks up
+        // the hashes `0..51` and expects them to wrap around the table.
+        let nodes = vec![("127.0.0.1:90", 1), ("127.0.0.1:91", 2)];
+        let ring = EnvoyRing::with_table_size(nodes, 17);
+        let expected = [1, 0, 0, 1, 0, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0, 1];
+        assert_eq!(ring.lookup, Lookup::Small(expected.to_vec()));
+
+        let mut entries = [0; 2];
+        for node in ring.lookup.iter() {
+            entries[node] += 1;
+        }
+        assert_eq!(entries, [6, 11]);
+
+        let hosts = ["127.0.0.1:90", "127.0.0.1:91"];
+        for hash in 0..51 {
+            let node = hosts[usize::from(expected[hash as usize % expected.len()])];
+            assert_eq!(ring.get_node_by_hash(hash), &node);
+        }
+    }
+
+    #[test]
+    fn test_envoy_default_table_size() {
+        // `MaglevLoadBalancerTest.DefaultMaglevTableSize` in Envoy's `maglev_lb_test.cc`.
+        let ring = EnvoyRing::new(vec![("127.0.0.1:90", 1)]);
+        assert_eq!(ring.capacity(), 65537);
+    }
+
+    // The expected entries below were generated by an independent implementation of the table
+    // construction in Envoy's `maglev_lb.cc` and only guard against regressions.
+
+    #[test]
+    fn test_envoy_equal_weights() {
+        let nodes = (0..3).map(|i| (format!("backend-{}", i), 1)).collect();
+        let ring = EnvoyRing::new(nodes);
+
+        let mut entries = [0; 3];
+        for node in ring.lookup.iter() {
+            entries[node] += 1;
+        }
+        assert_eq!(entries, [21846, 21846, 21845]);
+        let sample: Vec<usize> = (0..65537)
+            .step_by(4099)
+            .map(|i| ring.lookup.get(i))
+            .collect();
+        assert_eq!(sample, [1, 1, 0, 0, 1, 1, 1, 1, 2, 1, 0, 0, 0, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_envoy_try_with_table_size() {
+        let ring: Result<EnvoyRing<&str>, Error> = EnvoyRing::try_new(vec![]);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+
+        let ring = EnvoyRing::try_new(vec![("127.0.0.1:90", 0)]);
+        assert_eq!(ring.err(), Some(Error::InvalidWeight));
+
+        let ring = EnvoyRing::try_with_table_size(vec![("127.0.0.1:90", 1)], 65536);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+
+        let nodes = (0..6).map(|i| (format!("127.0.0.1:9{}", i), 1)).collect();
+        let ring = EnvoyRing::try_with_table_size(nodes, 5);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_envoy_serde() {
+        let ring = EnvoyRing::with_table_size(
+            vec![
+                (String::from("127.0.0.1:90"), 1),
+                (String::from("127.0.0.1:91"), 2),
+            ],
+            101,
+        );
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: EnvoyRing<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.lookup, ring.lookup);
+        assert_eq!(
+            other.iter().collect::<Vec<_>>(),
+            ring.iter().collect::<Vec<_>>()
+        );
+
+        let json = r#"{"capacity":100,"nodes":[["127.0.0.1:90",1]]}"#;
+        assert!(serde_json::from_str::<EnvoyRing<String>>(json).is_err());
+    }
+
+    fn flow_key(src_port: u16) -> FlowKey {
+        FlowKey {
+            src_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            src_port,
+            dst_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 1, 1)),
+            dst_port: 80,
+            protocol: 6,
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_flow_tracker_panic_new() {
+        let _: FlowTracker<u32> = FlowTracker::new(Ring::new(vec![0]), 0, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_flow_tracker_try_new() {
+        let tracker: Result<FlowTracker<u32>, Error> =
+            FlowTracker::try_new(Ring::new(vec![0]), 0, Duration::from_secs(1));
+        assert_eq!(tracker.err(), Some(Error::InvalidCapacity));
+    }
+
+    #[test]
+    fn test_flow_tracker_get_node() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2, 3], 1000);
+        let mut tracker = FlowTracker::new(ring, 100, Duration::from_secs(60));
+        for port in 0..50 {
+            let key = flow_key(port);
+            let node = *tracker.ring().get_node(&key);
+            assert_eq!(tracker.get_node(&key), &node);
+            assert_eq!(tracker.get_node(&key), &node);
+        }
+        assert_eq!(tracker.len(), 50);
+    }
+
+    #[test]
+    fn test_flow_tracker_set_ring() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2, 3], 1000);
+        let mut tracker = FlowTracker::new(ring, 1000, Duration::from_secs(60));
+        let now = Instant::now();
+        let nodes: Vec<u32> = (0..500)
+            .map(|port| *tracker.get_node_at(&flow_key(port), now))
+            .collect();
+
+        // Established flows keep their nodes even if the rebuilt ring maps them elsewhere.
+        let (ring, _) = tracker.ring().rebuild(vec![0, 1, 2, 3, 4]);
+        let moved = (0..500)
+            .filter(|port| ring.get_node(&flow_key(*port)) != &nodes[*port as usize])
+            .count();
+        assert!(moved > 0);
+        tracker.set_ring(ring);
+        for port in 0..500 {
+            let node = *tracker.get_node_at(&flow_key(port), now);
+            assert_eq!(node, nodes[port as usize]);
+        }
+
+        // Flows of removed nodes are looked up in the new ring.
+        let (ring, _) = tracker.ring().rebuild(vec![0, 1, 2, 4]);
+        tracker.set_ring(ring);
+        assert_eq!(
+            tracker.len(),
+            nodes.iter().filter(|node| **node != 3).count(),
+        );
+        for port in 0..500 {
+            let node = *tracker.get_node_at(&flow_key(port), now);
+            if nodes[port as usize] == 3 {
+                assert_eq!(&node, tracker.ring().get_node(&flow_key(port)));
+            } else {
+                assert_eq!(node, nodes[port as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flow_tracker_lru_eviction() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 1000);
+        let mut tracker = FlowTracker::new(ring, 3, Duration::from_secs(60));
+        let now = Instant::now();
+
+        tracker.get_node_at(&flow_key(0), now);
+        tracker.get_node_at(&flow_key(1), now);
+        tracker.get_node_at(&flow_key(2), now);
+        tracker.get_node_at(&flow_key(0), now);
+        tracker.get_node_at(&flow_key(3), now);
+
+        assert_eq!(tracker.len(), 3);
+        assert!(tracker.remove_flow(&flow_key(1)).is_none());
+        assert!(tracker.remove_flow(&flow_key(0)).is_some());
+        assert!(tracker.remove_flow(&flow_key(2)).is_some());
+        assert!(tracker.remove_flow(&flow_key(3)).is_some());
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_flow_tracker_idle_eviction() {
+        let ring = Ring::with_capacity_hint(vec![0, 1, 2], 1000);
+        let mut tracker = FlowTracker::new(ring, 100, Duration::from_secs(60));
+        let now = Instant::now();
+
+        tracker.get_node_at(&flow_key(0), now);
+        tracker.get_node_at(&flow_key(1), now + Duration::from_secs(20));
+        tracker.get_node_at(&flow_key(0), now + Duration::from_secs(40));
+        tracker.get_node_at(&flow_key(2), now + Duration::from_secs(50));
+
+        assert_eq!(tracker.evict_idle(now + Duration::from_secs(79)), 0);
+        assert_eq!(tracker.evict_idle(now + Duration::from_secs(80)), 1);
+        assert!(tracker.remove_flow(&flow_key(1)).is_none());
+
+        tracker.get_node_at(&flow_key(3), now + Duration::from_secs(105));
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.remove_flow(&flow_key(0)).is_none());
+    }
 }
@@ -0,0 +1,758 @@
+//! Hashing ring implemented using memento hashing.
+
+use crate::jump;
+use crate::util;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+/// A hashing ring implemented using memento hashing.
+///
+/// Memento hashing extends jump hashing to support removing arbitrary nodes. Keys are first jump
+/// hashed over all nodes that have ever been added. Each removed node is remembered in a
+/// replacement table together with the number of nodes that remained when it was removed, so a key
+/// that lands on a removed node is rehashed over the nodes that were working at that time. Removing
+/// a node only moves the keys of that node, and the memory overhead is proportional to the number
+/// of removed nodes.
+///
+/// Like jump hashing, nodes are integers in the range [0, n). Adding a node restores the most
+/// recently removed node, or appends a new node if no node is removed, which moves keys back to it
+/// exactly as they were before it was removed.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::memento::Ring;
+///
+/// let mut ring = Ring::new(10);
+///
+/// let node = ring.get_node(&"foo");
+/// ring.remove_node(node);
+/// assert_ne!(ring.get_node(&"foo"), node);
+///
+/// assert_eq!(ring.add_node(), node);
+/// assert_eq!(ring.get_node(&"foo"), node);
+/// ```
+#[derive(Clone)]
+pub struct Ring<H = StableBuildHasher> {
+    table: Table,
+    hash_builder: H,
+}
+
+// The node that replaced a removed node, which is also the number of nodes that remained after
+// its removal, and the node that was removed before it.
+#[derive(Clone)]
+struct Replacement {
+    replacer: u32,
+    previous: u32,
+}
+
+// The replacement table of memento hashing. `size` is the number of nodes that jump hashing is
+// applied over, and the removed nodes form a list from `last_removed` through their `previous`
+// nodes.
+#[derive(Clone)]
+struct Table {
+    size: u32,
+    last_removed: u32,
+    removed: HashMap<u32, Replacement>,
+}
+
+impl Table {
+    fn get_replacer(&self, node: u32) -> Option<u32> {
+        self.removed
+            .get(&node)
+            .map(|replacement| replacement.replacer)
+    }
+
+    fn get_node<H>(&self, hash_builder: &H, hash: u64) -> u32
+    where
+        H: BuildHasher,
+    {
+        let mut node = jump::jump(hash, u64::from(self.size)) as u32;
+        let mut replacer = self.get_replacer(node);
+        while let Some(working) = replacer {
+            // Rehash the key over the nodes that were working when `node` was removed. Nodes that
+            // were removed before `node` have a larger replacer and are followed to the node that
+            // replaced them at that time.
+            node = (util::combine_hash(hash_builder, hash, u64::from(node)) % u64::from(working))
+                as u32;
+            replacer = self.get_replacer(node);
+            while let Some(next) = replacer.filter(|next| *next >= working) {
+                node = next;
+                replacer = self.get_replacer(node);
+            }
+        }
+        node
+    }
+
+    fn add_node(&mut self) -> u32 {
+        let node = self.last_removed;
+        self.last_removed = match self.removed.remove(&node) {
+            Some(replacement) => replacement.previous,
+            None => node + 1,
+        };
+        if self.size <= node {
+            self.size = node + 1;
+        }
+        node
+    }
+
+    fn remove_node(&mut self, node: u32) {
+        // Removing the last node without any removed nodes is the same as shrinking jump hashing.
+        if self.removed.is_empty() && node == self.size - 1 {
+            self.size = node;
+            self.last_removed = node;
+            return;
+        }
+
+        let replacement = Replacement {
+            replacer: self.len() as u32 - 1,
+            previous: self.last_removed,
+        };
+        self.removed.insert(node, replacement);
+        self.last_removed = node;
+    }
+
+    fn contains(&self, node: u32) -> bool {
+        node < self.size && !self.removed.contains_key(&node)
+    }
+
+    fn len(&self) -> usize {
+        self.size as usize - self.removed.len()
+    }
+}
+
+impl Ring<StableBuildHasher> {
+    /// Constructs a new `Ring` with a specified number of nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of nodes is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let ring: Ring = Ring::new(100);
+    /// ```
+    pub fn new(nodes: u32) -> Self {
+        Self::with_hasher(Default::default(), nodes)
+    }
+
+    /// Constructs a new `Ring` with a specified number of nodes.
+    ///
+    /// Returns `Error::EmptyRing` if the number of nodes is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(Ring::try_new(100).is_ok());
+    /// assert_eq!(Ring::try_new(0).err(), Some(Error::EmptyRing));
+    /// ```
+    pub fn try_new(nodes: u32) -> Result<Self, Error> {
+        Self::try_with_hasher(Default::default(), nodes)
+    }
+}
+
+impl<H> Ring<H> {
+    /// Constructs a new `Ring` with a specified number of nodes and hash builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of nodes is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring: Ring<_> = Ring::with_hasher(DefaultBuildHasher::default(), 100);
+    /// ```
+    pub fn with_hasher(hash_builder: H, nodes: u32) -> Self {
+        match Self::try_with_hasher(hash_builder, nodes) {
+            Ok(ring) => ring,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Constructs a new `Ring` with a specified number of nodes and hash builder.
+    ///
+    /// Returns `Error::EmptyRing` if the number of nodes is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::Error;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring = Ring::try_with_hasher(DefaultBuildHasher::default(), 0);
+    /// assert_eq!(ring.err(), Some(Error::EmptyRing));
+    /// ```
+    pub fn try_with_hasher(hash_builder: H, nodes: u32) -> Result<Self, Error> {
+        if nodes == 0 {
+            return Err(Error::EmptyRing);
+        }
+        Ok(Self {
+            table: Table {
+                size: nodes,
+                last_removed: nodes,
+                removed: HashMap::new(),
+            },
+            hash_builder,
+        })
+    }
+
+    /// Returns the node associated with a key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all nodes are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring = Ring::with_hasher(DefaultBuildHasher::default(), 100);
+    /// assert_eq!(ring.get_node(&"foo"), 8);
+    /// ```
+    pub fn get_node<T>(&self, key: &T) -> u32
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        match self.try_get_node(key) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a key.
+    ///
+    /// Returns `Error::EmptyRing` if all nodes are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(1);
+    /// assert_eq!(ring.try_get_node(&"foo"), Ok(0));
+    ///
+    /// ring.remove_node(0);
+    /// assert_eq!(ring.try_get_node(&"foo"), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_get_node<T>(&self, key: &T) -> Result<u32, Error>
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, key))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a key in order of preference.
+    ///
+    /// The first node is the node returned by `get_node`, and each following node is the node that
+    /// the key would move to if all previous nodes were removed in order. Fewer than `n` nodes are
+    /// returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(10);
+    ///
+    /// let nodes = ring.get_nodes(&"foo", 3);
+    /// assert_eq!(nodes.len(), 3);
+    /// assert_eq!(nodes[0], ring.get_node(&"foo"));
+    ///
+    /// ring.remove_node(nodes[0]);
+    /// assert_eq!(ring.get_node(&"foo"), nodes[1]);
+    /// ```
+    pub fn get_nodes<T>(&self, key: &T, n: usize) -> Vec<u32>
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, key), n)
+    }
+
+    /// Returns the node associated with a hash of a key. The hash of a key is the value returned by
+    /// `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same node as
+    /// `get_node` for the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all nodes are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(10);
+    /// let hash = StableBuildHasher::new().hash_one(&"foo");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"foo"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> u32
+    where
+        H: BuildHasher,
+    {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a key.
+    ///
+    /// Returns `Error::EmptyRing` if all nodes are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(1);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(0));
+    ///
+    /// ring.remove_node(0);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<u32, Error>
+    where
+        H: BuildHasher,
+    {
+        if self.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        Ok(self.table.get_node(&self.hash_builder, hash))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a key in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let ring = Ring::new(10);
+    /// let hash = StableBuildHasher::new().hash_one(&"foo");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"foo", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<u32>
+    where
+        H: BuildHasher,
+    {
+        let n = cmp::min(n, self.len());
+        let mut table = self.table.clone();
+        let mut ret = Vec::with_capacity(n);
+        for _ in 0..n {
+            let node = table.get_node(&self.hash_builder, hash);
+            table.remove_node(node);
+            ret.push(node);
+        }
+        ret
+    }
+
+    /// Adds a node to the ring and returns it. The most recently removed node is restored if a node
+    /// has been removed, otherwise a new node is appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(10);
+    /// assert_eq!(ring.add_node(), 10);
+    ///
+    /// ring.remove_node(3);
+    /// ring.remove_node(7);
+    /// assert_eq!(ring.add_node(), 7);
+    /// assert_eq!(ring.add_node(), 3);
+    /// assert_eq!(ring.add_node(), 11);
+    /// ```
+    pub fn add_node(&mut self) -> u32 {
+        self.table.add_node()
+    }
+
+    /// Removes a node from the ring. Only the keys associated with the node are moved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(10);
+    /// ring.remove_node(3);
+    /// assert_eq!(ring.len(), 9);
+    /// ```
+    pub fn remove_node(&mut self, node: u32) {
+        if let Err(err) = self.try_remove_node(node) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Removes a node from the ring. Only the keys associated with the node are moved.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring = Ring::new(10);
+    /// assert_eq!(ring.try_remove_node(3), Ok(()));
+    /// assert_eq!(ring.try_remove_node(3), Err(Error::UnknownNode));
+    /// assert_eq!(ring.try_remove_node(10), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, node: u32) -> Result<(), Error> {
+        if !self.contains(node) {
+            return Err(Error::UnknownNode);
+        }
+        self.table.remove_node(node);
+        Ok(())
+    }
+
+    /// Returns `true` if the node exists in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(10);
+    /// ring.remove_node(3);
+    ///
+    /// assert!(ring.contains(2));
+    /// assert!(!ring.contains(3));
+    /// assert!(!ring.contains(10));
+    /// ```
+    pub fn contains(&self, node: u32) -> bool {
+        self.table.contains(node)
+    }
+
+    /// Returns the number of nodes in the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(10);
+    /// ring.remove_node(3);
+    /// assert_eq!(ring.len(), 9);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns `true` if all nodes are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(1);
+    ///
+    /// assert!(!ring.is_empty());
+    /// ring.remove_node(0);
+    /// assert!(ring.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the ring. The iterator will yield the nodes in the ring in
+    /// increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::memento::Ring;
+    ///
+    /// let mut ring = Ring::new(3);
+    /// ring.remove_node(1);
+    ///
+    /// let mut iterator = ring.iter();
+    /// assert_eq!(iterator.next(), Some(0));
+    /// assert_eq!(iterator.next(), Some(2));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.table.size).filter(move |node| !self.table.removed.contains_key(node))
+    }
+}
+
+impl<'a, H> IntoIterator for &'a Ring<H> {
+    type IntoIter = Box<dyn Iterator<Item = u32> + 'a>;
+    type Item = u32;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(self.iter())
+    }
+}
+
+/// Serializes the number of nodes that have been added to the ring and the removed nodes in the
+/// order that they were removed. The hash builder is not serialized.
+#[cfg(feature = "serde")]
+impl<H> Serialize for Ring<H> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State {
+            nodes: u32,
+            removed: Vec<u32>,
+        }
+
+        // Walk the list of removed nodes from the most recently removed node.
+        let mut removed = Vec::with_capacity(self.table.removed.len());
+        let mut node = self.table.last_removed;
+        while let Some(replacement) = self.table.removed.get(&node) {
+            removed.push(node);
+            node = replacement.previous;
+        }
+        removed.reverse();
+
+        State {
+            nodes: self.table.size,
+            removed,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the number of nodes that have been added to it and its removed nodes
+/// by removing them in order. The ring is rebuilt with `H::default()`, so lookups are only
+/// preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, H> Deserialize<'de> for Ring<H>
+where
+    H: Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State {
+            nodes: u32,
+            #[serde(default)]
+            removed: Vec<u32>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut ring =
+            Self::try_with_hasher(H::default(), state.nodes).map_err(de::Error::custom)?;
+        for node in state.removed {
+            ring.try_remove_node(node).map_err(de::Error::custom)?;
+        }
+        Ok(ring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ring;
+    use crate::jump;
+    use crate::test_util::BuildDefaultHasher;
+    use crate::Error;
+
+    #[test]
+    #[should_panic]
+    fn test_new_zero_nodes() {
+        let _ring = Ring::with_hasher(BuildDefaultHasher::default(), 0);
+    }
+
+    #[test]
+    fn test_try_new_zero_nodes() {
+        let ring = Ring::try_with_hasher(BuildDefaultHasher::default(), 0);
+        assert_eq!(ring.err(), Some(Error::EmptyRing));
+    }
+
+    #[test]
+    fn test_get_node_matches_jump() {
+        let ring = Ring::with_hasher(BuildDefaultHasher::default(), 100);
+        let jump = jump::Ring::with_hasher(BuildDefaultHasher::default(), 100);
+        for point in 0..1000 {
+            assert_eq!(u64::from(ring.get_node(&point)), jump.get_node(&point));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_node_unknown_node() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 10);
+        ring.remove_node(10);
+    }
+
+    #[test]
+    fn test_try_remove_node() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        assert_eq!(ring.try_remove_node(0), Ok(()));
+        assert_eq!(ring.try_remove_node(0), Err(Error::UnknownNode));
+        assert_eq!(ring.try_remove_node(2), Err(Error::UnknownNode));
+        assert_eq!(ring.try_get_node(&0), Ok(1));
+
+        assert_eq!(ring.try_remove_node(1), Ok(()));
+        assert!(ring.is_empty());
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+    }
+
+    #[test]
+    fn test_remove_node_moves_only_its_keys() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 20);
+        let mut expected: Vec<u32> = (0..10_000).map(|point| ring.get_node(&point)).collect();
+
+        for &node in &[3, 17, 8, 19, 0, 11] {
+            ring.remove_node(node);
+            let mut counts = [0; 20];
+            for point in 0..10_000u32 {
+                let actual = ring.get_node(&point);
+                assert!(ring.contains(actual));
+                if expected[point as usize] != node {
+                    assert_eq!(actual, expected[point as usize]);
+                }
+                expected[point as usize] = actual;
+                counts[actual as usize] += 1;
+            }
+
+            let average = 10_000 / ring.len();
+            for working in ring.iter() {
+                let count = counts[working as usize];
+                assert!(count > average * 8 / 10 && count < average * 12 / 10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_node_restores_removed_nodes() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 20);
+        let mut states = Vec::new();
+        for &node in &[3, 17, 8, 19, 0, 11] {
+            states.push(
+                (0..1000)
+                    .map(|point| ring.get_node(&point))
+                    .collect::<Vec<_>>(),
+            );
+            ring.remove_node(node);
+        }
+
+        for &node in [3, 17, 8, 19, 0, 11].iter().rev() {
+            assert_eq!(ring.add_node(), node);
+            let state = states.pop().unwrap();
+            for point in 0..1000 {
+                assert_eq!(ring.get_node(&point), state[point as usize]);
+            }
+        }
+
+        assert_eq!(ring.add_node(), 20);
+        assert_eq!(ring.len(), 21);
+    }
+
+    #[test]
+    fn test_remove_last_node() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 10);
+        ring.remove_node(9);
+        assert!(ring.table.removed.is_empty());
+
+        let jump = jump::Ring::with_hasher(BuildDefaultHasher::default(), 9);
+        for point in 0..1000 {
+            assert_eq!(u64::from(ring.get_node(&point)), jump.get_node(&point));
+        }
+        assert_eq!(ring.add_node(), 9);
+    }
+
+    #[test]
+    fn test_get_nodes() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 10);
+        ring.remove_node(4);
+
+        for point in 0..100 {
+            let nodes = ring.get_nodes(&point, 20);
+            let mut sorted = nodes.clone();
+            sorted.sort();
+            assert_eq!(sorted, ring.iter().collect::<Vec<_>>());
+            assert_eq!(ring.get_nodes(&point, 3)[..], nodes[..3]);
+
+            let mut other = Ring::with_hasher(BuildDefaultHasher::default(), 10);
+            other.remove_node(4);
+            for node in nodes {
+                assert_eq!(other.get_node(&point), node);
+                other.remove_node(node);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 5);
+        ring.remove_node(1);
+        ring.remove_node(3);
+
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [0, 2, 4]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 20);
+        for &node in &[3, 17, 8, 19, 0, 11] {
+            ring.remove_node(node);
+        }
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let mut other: Ring<BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            other.iter().collect::<Vec<_>>(),
+            ring.iter().collect::<Vec<_>>()
+        );
+        for point in 0..1000 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+        assert_eq!(other.add_node(), 11);
+
+        let json = r#"{"nodes":2,"removed":[0,0]}"#;
+        assert!(serde_json::from_str::<Ring<BuildDefaultHasher>>(json).is_err());
+    }
+}
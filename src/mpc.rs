@@ -1,11 +1,11 @@
 //! Hashing ring implemented using multi-probe consistent hashing.
 
 use crate::util;
-use rand::{Rng, XorShiftRng};
-use siphasher::sip::SipHasher;
-use std::collections::hash_map::RandomState;
-use std::collections::BTreeMap;
-use std::hash::{BuildHasher, Hash, Hasher};
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
 
 const PRIME: u64 = 0xFFFF_FFFF_FFFF_FFC5;
 
@@ -15,6 +15,10 @@ const PRIME: u64 = 0xFFFF_FFFF_FFFF_FFC5;
 /// being hashed multiple times to reduce variance, the keys are hashed multiple times. Each key is
 /// hashed `hash_count` times and the closest node over all hashes is returned.
 ///
+/// Nodes are weighted by inserting them with a number of replicas. Each replica is a separate point
+/// on the ring, so a node with three replicas receives approximately three times more keys than a
+/// node with one replica. The first replica of a node is placed at the hash of the node.
+///
 /// # Examples
 /// ```
 /// use hash_rings::mpc::Ring;
@@ -25,8 +29,8 @@ const PRIME: u64 = 0xFFFF_FFFF_FFFF_FFC5;
 ///
 /// let mut ring = Ring::with_hasher(DefaultBuildHasher::default(), 2);
 ///
-/// ring.insert_node(&"node-1");
-/// ring.insert_node(&"node-2");
+/// ring.insert_node("node-1", 1);
+/// ring.insert_node("node-2", 3);
 ///
 /// ring.remove_node(&"node-1");
 ///
@@ -34,19 +38,25 @@ const PRIME: u64 = 0xFFFF_FFFF_FFFF_FFC5;
 /// assert_eq!(ring.len(), 1);
 ///
 /// let mut iterator = ring.iter();
-/// assert_eq!(iterator.next(), Some(&"node-2"));
+/// assert_eq!(iterator.next(), Some((&"node-2", 3)));
 /// assert_eq!(iterator.next(), None);
 /// ```
-pub struct Ring<'a, T, H = RandomState> {
-    nodes: BTreeMap<u64, &'a T>,
+#[derive(Clone)]
+pub struct Ring<T, H = StableBuildHasher> {
+    nodes: BTreeMap<u64, T>,
+    replicas: HashMap<T, usize>,
     hash_count: u64,
-    hashers: [SipHasher; 2],
+    seed: u64,
     hash_builder: H,
 }
 
-impl<'a, T> Ring<'a, T, RandomState> {
+impl<T> Ring<T, StableBuildHasher> {
     /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted.
     ///
+    /// # Panics
+    ///
+    /// Panics if `hash_count` is zero.
+    ///
     /// # Examples
     ///
     /// ```
@@ -55,41 +65,51 @@ impl<'a, T> Ring<'a, T, RandomState> {
     /// let mut ring: Ring<&str> = Ring::new(2);
     /// ```
     pub fn new(hash_count: u64) -> Self {
-        assert!(hash_count > 0);
-        Self {
-            nodes: BTreeMap::new(),
-            hash_count,
-            hashers: Self::get_hashers(),
-            hash_builder: Default::default(),
-        }
+        Self::with_hasher(Default::default(), hash_count)
+    }
+
+    /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted.
+    ///
+    /// Returns `Error::InvalidCapacity` if `hash_count` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// assert!(Ring::<&str>::try_new(2).is_ok());
+    /// assert_eq!(Ring::<&str>::try_new(0).err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_new(hash_count: u64) -> Result<Self, Error> {
+        Self::try_with_hasher(Default::default(), hash_count)
     }
 }
 
-impl<'a, T, H> Ring<'a, T, H> {
-    fn get_hashers() -> [SipHasher; 2] {
-        let mut rng = XorShiftRng::new_unseeded();
+impl<T, H> Ring<T, H> {
+    // Derives the two hashes used for double hashing from a single hash of the item, so the
+    // probes are determined by the hash builder and the seed.
+    fn get_hashes(&self, hash: u64) -> [u64; 2]
+    where
+        H: BuildHasher,
+    {
         [
-            SipHasher::new_with_keys(rng.next_u64(), rng.next_u64()),
-            SipHasher::new_with_keys(rng.next_u64(), rng.next_u64()),
+            util::gen_hash(&self.hash_builder, &(hash, self.seed, 0u64)),
+            util::gen_hash(&self.hash_builder, &(hash, self.seed, 1u64)),
         ]
     }
 
-    fn get_hashes<U>(&self, item: &U) -> [u64; 2]
+    fn get_probes(&self, hash: u64) -> impl Iterator<Item = u64>
     where
-        U: Hash,
+        H: BuildHasher,
     {
-        let mut ret = [0; 2];
-        for (index, hash) in ret.iter_mut().enumerate() {
-            let mut sip = self.hashers[index];
-            item.hash(&mut sip);
-            *hash = sip.finish();
-        }
-        ret
+        let hashes = self.get_hashes(hash);
+        (0..self.hash_count).map(move |i| hashes[0].wrapping_add(i.wrapping_mul(hashes[1]) % PRIME))
     }
 
     fn get_distance(hash: u64, next_hash: u64) -> u64 {
         if hash > next_hash {
-            next_hash + (<u64>::max_value() - hash)
+            next_hash + (u64::MAX - hash)
         } else {
             next_hash - hash
         }
@@ -108,9 +128,29 @@ impl<'a, T, H> Ring<'a, T, H> {
         }
     }
 
+    fn get_replica_hash(&self, id: &T, replica: usize) -> u64
+    where
+        T: Hash,
+        H: BuildHasher,
+    {
+        let hash = util::gen_hash(&self.hash_builder, id);
+        if replica == 0 {
+            return hash;
+        }
+        util::combine_hash(
+            &self.hash_builder,
+            hash,
+            util::gen_hash(&self.hash_builder, &replica),
+        )
+    }
+
     /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted
     /// with a specified hash builder.
     ///
+    /// # Panics
+    ///
+    /// Panics if `hash_count` is zero.
+    ///
     /// # Examples
     ///
     /// ```
@@ -123,39 +163,163 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str, _> = Ring::with_hasher(DefaultBuildHasher::default(), 2);
     /// ```
     pub fn with_hasher(hash_builder: H, hash_count: u64) -> Self {
+        Self::with_seed(hash_builder, hash_count, 0)
+    }
+
+    /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted
+    /// with a specified hash builder.
+    ///
+    /// Returns `Error::InvalidCapacity` if `hash_count` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    ///
+    /// type DefaultBuildHasher = BuildHasherDefault<DefaultHasher>;
+    ///
+    /// let ring: Result<Ring<&str, _>, _> = Ring::try_with_hasher(DefaultBuildHasher::default(), 0);
+    /// assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_hasher(hash_builder: H, hash_count: u64) -> Result<Self, Error> {
+        if hash_count == 0 {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self::with_hasher(hash_builder, hash_count))
+    }
+
+    /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted
+    /// with a specified hash builder and seed.
+    ///
+    /// The probes of a key are derived from its hash and the seed, so rings over the same nodes
+    /// with different seeds map keys to nodes independently. A seed of zero is equivalent to
+    /// `with_hasher`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hash_count` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let mut ring: Ring<&str, _> = Ring::with_seed(StableBuildHasher::new(), 2, 42);
+    /// ```
+    pub fn with_seed(hash_builder: H, hash_count: u64, seed: u64) -> Self {
         assert!(hash_count > 0);
         Self {
             nodes: BTreeMap::new(),
+            replicas: HashMap::new(),
             hash_count,
-            hashers: Self::get_hashers(),
+            seed,
             hash_builder,
         }
     }
 
-    /// Inserts a node into the ring with a number of replicas.
+    /// Constructs a new, empty `Ring<T>` that hashes `hash_count` times when a key is inserted
+    /// with a specified hash builder and seed.
+    ///
+    /// Returns `Error::InvalidCapacity` if `hash_count` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::{Error, StableBuildHasher};
+    ///
+    /// let ring: Result<Ring<&str, _>, _> = Ring::try_with_seed(StableBuildHasher::new(), 0, 42);
+    /// assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    /// ```
+    pub fn try_with_seed(hash_builder: H, hash_count: u64, seed: u64) -> Result<Self, Error> {
+        if hash_count == 0 {
+            return Err(Error::InvalidCapacity);
+        }
+        Ok(Self::with_seed(hash_builder, hash_count, seed))
+    }
+
+    /// Returns the seed used to probe the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::StableBuildHasher;
+    ///
+    /// let ring: Ring<&str, _> = Ring::with_seed(StableBuildHasher::new(), 2, 42);
+    /// assert_eq!(ring.seed(), 42);
+    /// ```
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Inserts a node into the ring with a number of replicas. If the node already exists, its
+    /// replicas are replaced.
     ///
     /// Increasing the number of replicas will increase the number of expected points mapped to the
     /// node. For example, a node with three replicas will receive approximately three times more
     /// points than a node with one replica.
     ///
+    /// # Panics
+    ///
+    /// Panics if the number of replicas is zero.
+    ///
     /// # Examples
     ///
     /// ```
     /// use hash_rings::mpc::Ring;
     ///
     /// let mut ring: Ring<&str> = Ring::new(2);
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 3);
     /// ```
-    pub fn insert_node(&mut self, id: &'a T)
+    pub fn insert_node(&mut self, id: T, replicas: usize)
     where
-        T: Hash,
+        T: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if let Err(err) = self.try_insert_node(id, replicas) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Inserts a node into the ring with a number of replicas. If the node already exists, its
+    /// replicas are replaced.
+    ///
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    ///
+    /// assert_eq!(ring.try_insert_node("node-1", 1), Ok(()));
+    /// assert_eq!(ring.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<(), Error>
+    where
+        T: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        self.nodes
-            .insert(util::gen_hash(&self.hash_builder, id), id);
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        self.remove_node(&id);
+        for i in 0..replicas {
+            let hash = self.get_replica_hash(&id, i);
+            self.nodes.insert(hash, id.clone());
+        }
+        self.replicas.insert(id, replicas);
+        Ok(())
     }
 
-    /// Removes a node.
+    /// Removes a node and all its replicas from the ring. Does nothing if the node does not exist.
     ///
     /// # Examples
     ///
@@ -164,15 +328,47 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new(2);
     ///
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 1);
     /// ring.remove_node(&"node-1");
     /// ```
     pub fn remove_node(&mut self, id: &T)
     where
-        T: Hash,
+        T: Hash + Eq,
+        H: BuildHasher,
+    {
+        let _ = self.try_remove_node(id);
+    }
+
+    /// Removes a node and all its replicas from the ring.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Ok(()));
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<(), Error>
+    where
+        T: Hash + Eq,
         H: BuildHasher,
     {
-        self.nodes.remove(&util::gen_hash(&self.hash_builder, id));
+        let replicas = self.replicas.remove(id).ok_or(Error::UnknownNode)?;
+        for i in 0..replicas {
+            let hash = self.get_replica_hash(id, i);
+            // Replicas of different nodes whose hashes collide overwrite each other.
+            if self.nodes.get(&hash) == Some(id) {
+                self.nodes.remove(&hash);
+            }
+        }
+        Ok(())
     }
 
     /// Returns the node associated with a point.
@@ -188,24 +384,187 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new(2);
     ///
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 1);
     /// assert_eq!(ring.get_node(&"point-1"), &"node-1");
     /// ```
     pub fn get_node<U>(&self, point: &U) -> &T
     where
         U: Hash,
+        H: BuildHasher,
+    {
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+        H: BuildHasher,
     {
-        let hashes = self.get_hashes(point);
-        let hash = (0..self.hash_count)
-            .map(|i| {
-                let hash = hashes[0].wrapping_add((i as u64).wrapping_mul(hashes[1]) % PRIME);
-                let next_hash = self.get_next_hash(hash);
-                (Self::get_distance(hash, next_hash), next_hash)
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are ordered by their smallest clockwise distance from any of the probes of the
+    /// point, so the first node is the node returned by `get_node`. Fewer than `n` nodes are
+    /// returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, point), n)
+    }
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same
+    /// node as `get_node` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T
+    where
+        H: BuildHasher,
+    {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error>
+    where
+        H: BuildHasher,
+    {
+        if self.nodes.is_empty() {
+            return Err(Error::EmptyRing);
+        }
+        let hash = self
+            .get_probes(hash)
+            .map(|probe| {
+                let next_hash = self.get_next_hash(probe);
+                (Self::get_distance(probe, next_hash), next_hash)
             })
             .min()
             .expect("Error: expected positive hash count.");
 
-        self.nodes[&hash.1]
+        Ok(&self.nodes[&hash.1])
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::mpc::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new(2);
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T>
+    where
+        T: Hash + Eq,
+        H: BuildHasher,
+    {
+        // A node that is among the `n` closest nodes overall must have a replica among the `n`
+        // closest distinct nodes clockwise from the probe it is closest to.
+        let mut candidates = Vec::new();
+        for probe in self.get_probes(hash) {
+            let mut seen = HashSet::new();
+            candidates.extend(
+                self.nodes
+                    .range(probe..)
+                    .chain(self.nodes.range(..probe))
+                    .filter(|entry| seen.insert(entry.1))
+                    .take(n)
+                    .map(|entry| (Self::get_distance(probe, *entry.0), *entry.0, entry.1)),
+            );
+        }
+        candidates.sort_by_key(|candidate| (candidate.0, candidate.1));
+
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|candidate| seen.insert(candidate.2))
+            .take(n)
+            .map(|candidate| candidate.2)
+            .collect()
     }
 
     /// Returns the number of nodes in the ring.
@@ -217,11 +576,14 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new(2);
     ///
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 1);
     /// assert_eq!(ring.len(), 1);
     /// ```
-    pub fn len(&self) -> usize {
-        self.nodes.len()
+    pub fn len(&self) -> usize
+    where
+        T: Hash + Eq,
+    {
+        self.replicas.len()
     }
 
     /// Returns `true` if the ring is empty.
@@ -234,15 +596,18 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str> = Ring::new(2);
     ///
     /// assert!(ring.is_empty());
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 1);
     /// assert!(!ring.is_empty());
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.nodes.is_empty()
+    pub fn is_empty(&self) -> bool
+    where
+        T: Hash + Eq,
+    {
+        self.replicas.is_empty()
     }
 
-    /// Returns an iterator over the ring. The iterator will yield the nodes in the ring in no
-    /// particular order.
+    /// Returns an iterator over the ring. The iterator will yield the nodes and their number of
+    /// replicas in no particular order.
     ///
     /// # Examples
     ///
@@ -250,67 +615,228 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// use hash_rings::mpc::Ring;
     ///
     /// let mut ring = Ring::new(2);
-    /// ring.insert_node(&"node-1");
+    /// ring.insert_node("node-1", 1);
     ///
     /// let mut iterator = ring.iter();
-    /// assert_eq!(iterator.next(), Some(&"node-1"));
+    /// assert_eq!(iterator.next(), Some((&"node-1", 1)));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = &'a T> {
-        self.nodes.iter().map(|node| *node.1)
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)>
+    where
+        T: Hash + Eq,
+    {
+        self.replicas.iter().map(|replica| (replica.0, *replica.1))
     }
 }
 
-impl<'a, T, H> IntoIterator for &'a Ring<'a, T, H>
+impl<'a, T, H> IntoIterator for &'a Ring<T, H>
 where
     T: Hash + Eq,
 {
-    type IntoIter = Box<dyn Iterator<Item = &'a T> + 'a>;
-    type Item = (&'a T);
+    type IntoIter = Box<dyn Iterator<Item = (&'a T, usize)> + 'a>;
+    type Item = (&'a T, usize);
 
     fn into_iter(self) -> Self::IntoIter {
         Box::new(self.iter())
     }
 }
 
+/// Serializes the number of probes, the seed, and the ids and replicas of the nodes in the ring.
+/// The hash builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for Ring<T, H>
+where
+    T: Serialize + Hash + Eq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            hash_count: u64,
+            seed: u64,
+            nodes: Vec<&'a T>,
+            replicas: Vec<usize>,
+        }
+
+        let (nodes, replicas) = self.iter().unzip();
+        State {
+            hash_count: self.hash_count,
+            seed: self.seed,
+            nodes,
+            replicas,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the number of probes, the seed, and the ids and replicas of its nodes.
+/// Nodes have one replica if the replicas are missing. The ring is rebuilt with `H::default()`, so
+/// lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for Ring<T, H>
+where
+    T: Deserialize<'de> + Hash + Eq + Clone,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            hash_count: u64,
+            #[serde(default)]
+            seed: u64,
+            nodes: Vec<T>,
+            #[serde(default)]
+            replicas: Option<Vec<usize>>,
+        }
+
+        let state: State<T> = State::deserialize(deserializer)?;
+        let mut ring = Self::try_with_seed(H::default(), state.hash_count, state.seed)
+            .map_err(de::Error::custom)?;
+        let nodes = state.nodes;
+        let replicas = state.replicas.unwrap_or_else(|| vec![1; nodes.len()]);
+        if replicas.len() != nodes.len() {
+            return Err(de::Error::custom(Error::InvalidWeight));
+        }
+        for (id, replicas) in nodes.into_iter().zip(replicas) {
+            ring.try_insert_node(id, replicas)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(ring)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Ring;
     use crate::test_util::BuildDefaultHasher;
+    use crate::{util, Error, StableBuildHasher};
 
     #[test]
     #[should_panic]
     fn test_new_zero_hash_count() {
-        let _ring: Ring<'_, u32, BuildDefaultHasher> =
+        let _ring: Ring<u32, BuildDefaultHasher> =
             Ring::with_hasher(BuildDefaultHasher::default(), 0);
     }
 
     #[test]
     #[should_panic]
     fn test_get_node_empty_ring() {
-        let ring: Ring<'_, u32, BuildDefaultHasher> =
+        let ring: Ring<u32, BuildDefaultHasher> =
             Ring::with_hasher(BuildDefaultHasher::default(), 2);
         ring.get_node(&0);
     }
 
+    #[test]
+    fn test_try_get_node_empty_ring() {
+        let mut ring = Ring::try_with_hasher(BuildDefaultHasher::default(), 2).unwrap();
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+
+        ring.insert_node(0, 1);
+        assert_eq!(ring.try_get_node(&0), Ok(&0));
+        assert_eq!(ring.try_remove_node(&0), Ok(()));
+        assert_eq!(ring.try_remove_node(&0), Err(Error::UnknownNode));
+        assert_eq!(ring.try_get_node(&0), Err(Error::EmptyRing));
+    }
+
     #[test]
     fn test_get_node() {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
 
-        ring.insert_node(&0);
+        ring.insert_node(0, 1);
         assert_eq!(ring.get_node(&2), &0);
 
-        ring.insert_node(&1);
+        ring.insert_node(1, 1);
         assert_eq!(ring.get_node(&2), &1);
 
         ring.remove_node(&1);
         assert_eq!(ring.get_node(&2), &0);
     }
 
+    #[test]
+    fn test_get_nodes() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 3);
+        for node in 0..20u32 {
+            ring.insert_node(node, 1);
+        }
+
+        for point in 0..100u32 {
+            let mut expected: Vec<(u64, u64)> = ring
+                .nodes
+                .keys()
+                .map(|&node_hash| {
+                    let distance = ring
+                        .get_probes(util::gen_hash(&ring.hash_builder, &point))
+                        .map(|hash| Ring::<u32, BuildDefaultHasher>::get_distance(hash, node_hash))
+                        .min()
+                        .unwrap();
+                    (distance, node_hash)
+                })
+                .collect();
+            expected.sort();
+            let expected: Vec<&u32> = expected
+                .iter()
+                .take(5)
+                .map(|entry| &ring.nodes[&entry.1])
+                .collect();
+
+            assert_eq!(ring.get_nodes(&point, 5), expected);
+            assert_eq!(ring.get_nodes(&point, 1), [ring.get_node(&point)]);
+        }
+    }
+
+    #[test]
+    fn test_with_seed() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        let mut same = Ring::with_seed(BuildDefaultHasher::default(), 2, 0);
+        let mut other = Ring::with_seed(BuildDefaultHasher::default(), 2, 1);
+        for id in 0..10 {
+            ring.insert_node(id, 1);
+            same.insert_node(id, 1);
+            other.insert_node(id, 1);
+        }
+
+        let mut differences = 0;
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), same.get_node(&point));
+            if ring.get_node(&point) != other.get_node(&point) {
+                differences += 1;
+            }
+        }
+        assert!(differences > 50);
+    }
+
+    #[test]
+    fn test_with_hasher_probes() {
+        let ring: Ring<u32, _> = Ring::with_hasher(StableBuildHasher::with_keys(1, 2), 3);
+        let same: Ring<u32, _> = Ring::with_hasher(StableBuildHasher::with_keys(1, 2), 3);
+        let other: Ring<u32, _> = Ring::with_hasher(StableBuildHasher::with_keys(3, 4), 3);
+
+        for point in 0..100u32 {
+            let probes = |ring: &Ring<u32, StableBuildHasher>| -> Vec<u64> {
+                ring.get_probes(util::gen_hash(&ring.hash_builder, &point))
+                    .collect()
+            };
+            assert_eq!(probes(&ring), probes(&same));
+            assert_ne!(probes(&ring), probes(&other));
+        }
+    }
+
+    #[test]
+    fn test_try_with_seed_zero_hash_count() {
+        let ring: Result<Ring<u32, _>, _> =
+            Ring::try_with_seed(BuildDefaultHasher::default(), 0, 1);
+        assert_eq!(ring.err(), Some(Error::InvalidCapacity));
+    }
+
     #[test]
     fn test_len() {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
-        ring.insert_node(&0);
+        ring.insert_node(0, 1);
 
         assert_eq!(ring.len(), 1);
     }
@@ -320,17 +846,135 @@ mod tests {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
         assert!(ring.is_empty());
 
-        ring.insert_node(&0);
+        ring.insert_node(0, 1);
         assert!(!ring.is_empty());
     }
 
     #[test]
     fn test_iter() {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
-        ring.insert_node(&0);
+        ring.insert_node(0, 1);
 
         let mut iterator = ring.iter();
-        assert_eq!(iterator.next(), Some(&0));
+        assert_eq!(iterator.next(), Some((&0, 1)));
         assert_eq!(iterator.next(), None);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut ring = Ring::with_seed(BuildDefaultHasher::default(), 21, 42);
+        for id in 0..10u32 {
+            ring.insert_node(id, id as usize % 3 + 1);
+        }
+
+        let json = serde_json::to_string(&ring).unwrap();
+        let other: Ring<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.hash_count, 21);
+        assert_eq!(other.seed(), 42);
+        assert_eq!(other.nodes, ring.nodes);
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), other.get_node(&point));
+        }
+
+        let json = r#"{"hash_count":0,"nodes":[0]}"#;
+        assert!(serde_json::from_str::<Ring<u32, BuildDefaultHasher>>(json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_replicas() {
+        let json = r#"{"hash_count":2,"nodes":[0,1]}"#;
+        let ring: Ring<u32, BuildDefaultHasher> = serde_json::from_str(json).unwrap();
+        let mut nodes: Vec<(&u32, usize)> = ring.iter().collect();
+        nodes.sort();
+        assert_eq!(nodes, [(&0, 1), (&1, 1)]);
+
+        let json = r#"{"hash_count":2,"nodes":[0,1],"replicas":[1]}"#;
+        assert!(serde_json::from_str::<Ring<u32, BuildDefaultHasher>>(json).is_err());
+
+        let json = r#"{"hash_count":2,"nodes":[0],"replicas":[0]}"#;
+        assert!(serde_json::from_str::<Ring<u32, BuildDefaultHasher>>(json).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_node_zero_replicas() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        ring.insert_node(0, 0);
+    }
+
+    #[test]
+    fn test_try_insert_node() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        assert_eq!(ring.try_insert_node(0, 0), Err(Error::InvalidWeight));
+        assert!(ring.is_empty());
+
+        assert_eq!(ring.try_insert_node(0, 3), Ok(()));
+        assert_eq!(ring.nodes.len(), 3);
+    }
+
+    #[test]
+    fn test_insert_node_replace() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        ring.insert_node(0, 3);
+        ring.insert_node(0, 1);
+
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.nodes.len(), 1);
+        assert_eq!(ring.iter().collect::<Vec<_>>(), [(&0, 1)]);
+    }
+
+    #[test]
+    fn test_remove_node_replicas() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        ring.insert_node(0, 3);
+        ring.insert_node(1, 2);
+
+        ring.remove_node(&0);
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.nodes.len(), 2);
+        for point in 0..100 {
+            assert_eq!(ring.get_node(&point), &1);
+        }
+    }
+
+    #[test]
+    fn test_get_nodes_replicas() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 3);
+        for node in 0..10u32 {
+            ring.insert_node(node, 3);
+        }
+
+        for point in 0..100u32 {
+            let nodes = ring.get_nodes(&point, 5);
+            let mut distinct = nodes.clone();
+            distinct.sort();
+            distinct.dedup();
+
+            assert_eq!(distinct.len(), 5);
+            assert_eq!(nodes[0], ring.get_node(&point));
+            assert_eq!(ring.get_nodes(&point, 20).len(), 10);
+        }
+    }
+
+    #[test]
+    fn test_weighted_distribution() {
+        let mut ring = Ring::with_hasher(BuildDefaultHasher::default(), 21);
+        for id in 0..4u32 {
+            ring.insert_node(id, (id as usize + 1) * 4);
+        }
+
+        let mut counts = [0u32; 4];
+        for point in 0..100_000u32 {
+            counts[*ring.get_node(&point) as usize] += 1;
+        }
+
+        for (id, count) in counts.iter().enumerate() {
+            let expected = (id + 1) as f64 / 10.0;
+            let actual = f64::from(*count) / 100_000.0;
+            assert!((actual - expected).abs() / expected < 0.05);
+        }
+    }
 }
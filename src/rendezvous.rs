@@ -1,9 +1,11 @@
 //! Hashing ring implemented using rendezvous hashing.
 
 use crate::util;
-use std::collections::hash_map::RandomState;
+use crate::{Error, StableBuildHasher};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
-use std::hash::{BuildHasher, Hash};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::vec::Vec;
 
 /// A hashing ring implemented using rendezvous hashing.
@@ -23,8 +25,8 @@ use std::vec::Vec;
 ///
 /// let mut ring = Ring::with_hasher(DefaultBuildHasher::default());
 ///
-/// ring.insert_node(&"node-1", 1);
-/// ring.insert_node(&"node-2", 3);
+/// ring.insert_node("node-1", 1);
+/// ring.insert_node("node-2", 3);
 ///
 /// ring.remove_node(&"node-1");
 ///
@@ -35,12 +37,13 @@ use std::vec::Vec;
 /// assert_eq!(iterator.next(), Some((&"node-2", 3)));
 /// assert_eq!(iterator.next(), None);
 /// ```
-pub struct Ring<'a, T, H = RandomState> {
-    nodes: HashMap<&'a T, Vec<u64>>,
+#[derive(Clone)]
+pub struct Ring<T, H = StableBuildHasher> {
+    nodes: HashMap<T, Vec<u64>>,
     hash_builder: H,
 }
 
-impl<'a, T> Ring<'a, T, RandomState> {
+impl<T> Ring<T, StableBuildHasher> {
     /// Constructs a new, empty `Ring<T>`.
     ///
     /// # Examples
@@ -58,7 +61,7 @@ impl<'a, T> Ring<'a, T, RandomState> {
     }
 }
 
-impl<'a, T, H> Ring<'a, T, H> {
+impl<T, H> Ring<T, H> {
     /// Constructs a new, empty `Ring<T>` with a specified hash builder.
     ///
     /// # Examples
@@ -97,10 +100,10 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str> = Ring::new();
     ///
     /// // "node-2" will receive three times more points than "node-1"
-    /// ring.insert_node(&"node-1", 1);
-    /// ring.insert_node(&"node-2", 3);
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
     /// ```
-    pub fn insert_node(&mut self, id: &'a T, replicas: usize)
+    pub fn insert_node(&mut self, id: T, replicas: usize)
     where
         T: Hash + Eq,
         H: BuildHasher,
@@ -109,7 +112,7 @@ impl<'a, T, H> Ring<'a, T, H> {
             .map(|index| {
                 util::combine_hash(
                     &self.hash_builder,
-                    util::gen_hash(&self.hash_builder, id),
+                    util::gen_hash(&self.hash_builder, &id),
                     util::gen_hash(&self.hash_builder, &index),
                 )
             })
@@ -117,6 +120,33 @@ impl<'a, T, H> Ring<'a, T, H> {
         self.nodes.insert(id, hashes);
     }
 
+    /// Inserts a node into the ring with a number of replicas.
+    ///
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// assert_eq!(ring.try_insert_node("node-1", 1), Ok(()));
+    /// assert_eq!(ring.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<(), Error>
+    where
+        T: Hash + Eq,
+        H: BuildHasher,
+    {
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        self.insert_node(id, replicas);
+        Ok(())
+    }
+
     /// Removes a node and all its replicas from the ring.
     ///
     /// # Examples
@@ -126,8 +156,8 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 1);
-    /// ring.insert_node(&"node-2", 1);
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
     /// ring.remove_node(&"node-2");
     /// ```
     pub fn remove_node(&mut self, id: &T)
@@ -137,6 +167,29 @@ impl<'a, T, H> Ring<'a, T, H> {
         self.nodes.remove(id);
     }
 
+    /// Removes a node and all its replicas from the ring.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Ok(()));
+    /// assert_eq!(ring.try_remove_node(&"node-1"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<(), Error>
+    where
+        T: Hash + Eq,
+    {
+        self.nodes.remove(id).map(|_| ()).ok_or(Error::UnknownNode)
+    }
+
     /// Returns the node associated with a point.
     ///
     /// # Panics
@@ -150,32 +203,294 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 1);
+    /// ring.insert_node("node-1", 1);
     /// assert_eq!(ring.get_node(&"point-1"), &"node-1");
     /// ```
-    pub fn get_node<U>(&self, id: &U) -> &'a T
+    pub fn get_node<U>(&self, point: &U) -> &T
     where
         T: Hash + Ord,
         U: Hash,
         H: BuildHasher,
     {
-        let point_hash = util::gen_hash(&self.hash_builder, id);
-        self.nodes
+        match self.try_get_node(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring has no nodes with a non-zero number of replicas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        T: Hash + Ord,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.try_get_node_by_hash(util::gen_hash(&self.hash_builder, point))
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference.
+    ///
+    /// The nodes are ordered by decreasing score, so the first node is the node returned by
+    /// `get_node`. Fewer than `n` nodes are returned if the ring has fewer than `n` nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    ///
+    /// let nodes = ring.get_nodes(&"point-1", 3);
+    /// assert_eq!(nodes.len(), 2);
+    /// assert_eq!(nodes[0], ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Ord,
+        U: Hash,
+        H: BuildHasher,
+    {
+        self.get_nodes_by_hash(util::gen_hash(&self.hash_builder, point), n)
+    }
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so `get_node_by_hash` returns the same
+    /// node as `get_node` for the point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_node_by_hash(hash), ring.get_node(&"point-1"));
+    /// ```
+    pub fn get_node_by_hash(&self, hash: u64) -> &T
+    where
+        T: Hash + Ord,
+        H: BuildHasher,
+    {
+        match self.try_get_node_by_hash(hash) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the node associated with a hash of a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring has no nodes with a non-zero number of replicas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::Error;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    /// assert_eq!(ring.try_get_node_by_hash(1), Err(Error::EmptyRing));
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.try_get_node_by_hash(1), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error>
+    where
+        T: Hash + Ord,
+        H: BuildHasher,
+    {
+        self.get_scores(hash)
+            .max()
+            .map(|entry| entry.1)
+            .ok_or(Error::EmptyRing)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    /// use hash_rings::StableBuildHasher;
+    /// use std::hash::BuildHasher;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 1);
+    /// let hash = StableBuildHasher::new().hash_one(&"point-1");
+    ///
+    /// assert_eq!(ring.get_nodes_by_hash(hash, 2), ring.get_nodes(&"point-1", 2));
+    /// ```
+    pub fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T>
+    where
+        T: Hash + Ord,
+        H: BuildHasher,
+    {
+        let mut scores: Vec<(u64, &T)> = self.get_scores(hash).collect();
+        scores.sort_by(|n, m| m.cmp(n));
+        scores.into_iter().take(n).map(|entry| entry.1).collect()
+    }
+
+    /// Returns the nodes associated with a batch of points in the order of the points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring has no nodes with a non-zero number of
+    /// replicas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let points = ["point-1", "point-2", "point-3"];
+    /// let nodes = ring.get_nodes_batch(&points);
+    /// for (point, node) in points.iter().zip(nodes) {
+    ///     assert_eq!(node, ring.get_node(point));
+    /// }
+    /// ```
+    pub fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        T: Hash + Ord,
+        U: Hash,
+        H: BuildHasher,
+    {
+        points
             .iter()
-            .map(|entry| {
-                (
-                    entry
-                        .1
-                        .iter()
-                        .map(|hash| util::combine_hash(&self.hash_builder, *hash, point_hash))
-                        .max()
-                        .expect("Expected non-zero number of replicas."),
-                    entry.0,
-                )
+            .map(|point| self.get_node_by_hash(util::gen_hash(&self.hash_builder, point)))
+            .collect()
+    }
+
+    /// Returns the nodes associated with a batch of points in the order of the points, which are
+    /// the same nodes as those returned by `get_nodes_batch`.
+    ///
+    /// The hash of every replica is written to a hasher once for the whole batch, and blocks of
+    /// points are scored against the contiguous array of these hashers, so scoring a point against
+    /// a replica only clones a hasher and hashes the point instead of hashing both from scratch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring has no nodes with a non-zero number of
+    /// replicas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Ring;
+    ///
+    /// let mut ring: Ring<&str> = Ring::new();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// ring.insert_node("node-2", 3);
+    ///
+    /// let points = ["point-1", "point-2", "point-3"];
+    /// let nodes = ring.get_nodes_batch_prefixed(&points);
+    /// for (point, node) in points.iter().zip(nodes) {
+    ///     assert_eq!(node, ring.get_node(point));
+    /// }
+    /// ```
+    pub fn get_nodes_batch_prefixed<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        T: Hash + Ord,
+        U: Hash,
+        H: BuildHasher,
+        H::Hasher: Clone,
+    {
+        const BLOCK_SIZE: usize = 64;
+
+        if points.is_empty() {
+            return Vec::new();
+        }
+        let replicas: Vec<(H::Hasher, &T)> = self
+            .nodes
+            .iter()
+            .flat_map(|(id, hashes)| hashes.iter().map(move |hash| (*hash, id)))
+            .map(|(hash, id)| {
+                let mut hasher = self.hash_builder.build_hasher();
+                hash.hash(&mut hasher);
+                (hasher, id)
             })
-            .max()
-            .expect("Expected non-empty ring.")
-            .1
+            .collect();
+        let (first, rest) = match replicas.split_first() {
+            Some(split) => split,
+            None => panic!("Error: {}.", Error::EmptyRing),
+        };
+
+        let mut ret = Vec::with_capacity(points.len());
+        for block in points.chunks(BLOCK_SIZE) {
+            let point_hashes: Vec<u64> = block
+                .iter()
+                .map(|point| util::gen_hash(&self.hash_builder, point))
+                .collect();
+            let mut scores: Vec<(u64, &T)> = point_hashes
+                .iter()
+                .map(|point_hash| {
+                    let mut hasher = first.0.clone();
+                    point_hash.hash(&mut hasher);
+                    (hasher.finish(), first.1)
+                })
+                .collect();
+            for (prefix, id) in rest {
+                for (point_hash, max_score) in point_hashes.iter().zip(&mut scores) {
+                    let mut hasher = prefix.clone();
+                    point_hash.hash(&mut hasher);
+                    let score = (hasher.finish(), *id);
+                    if score > *max_score {
+                        *max_score = score;
+                    }
+                }
+            }
+            ret.extend(scores.into_iter().map(|entry| entry.1));
+        }
+        ret
+    }
+
+    fn get_scores(&self, point_hash: u64) -> impl Iterator<Item = (u64, &T)>
+    where
+        H: BuildHasher,
+    {
+        self.nodes.iter().filter_map(move |entry| {
+            entry
+                .1
+                .iter()
+                .map(|hash| util::combine_hash(&self.hash_builder, *hash, point_hash))
+                .max()
+                .map(|score| (score, entry.0))
+        })
     }
 
     fn get_hashes(&self, id: &T) -> Vec<u64>
@@ -194,7 +509,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     ///
     /// let mut ring: Ring<&str> = Ring::new();
     ///
-    /// ring.insert_node(&"node-1", 3);
+    /// ring.insert_node("node-1", 3);
     /// assert_eq!(ring.len(), 1);
     /// ```
     pub fn len(&self) -> usize
@@ -214,7 +529,7 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// let mut ring: Ring<&str> = Ring::new();
     ///
     /// assert!(ring.is_empty());
-    /// ring.insert_node(&"node-1", 3);
+    /// ring.insert_node("node-1", 3);
     /// assert!(!ring.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool
@@ -233,24 +548,24 @@ impl<'a, T, H> Ring<'a, T, H> {
     /// use hash_rings::rendezvous::Ring;
     ///
     /// let mut ring = Ring::new();
-    /// ring.insert_node(&"node-1", 1);
+    /// ring.insert_node("node-1", 1);
     ///
     /// let mut iterator = ring.iter();
     /// assert_eq!(iterator.next(), Some((&"node-1", 1)));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = (&'a T, usize)>
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)>
     where
         T: Hash + Eq,
     {
         self.nodes.iter().map(|node_entry| {
             let (id, hashes) = node_entry;
-            (&**id, hashes.len())
+            (id, hashes.len())
         })
     }
 }
 
-impl<'a, T, H> IntoIterator for &'a Ring<'a, T, H>
+impl<'a, T, H> IntoIterator for &'a Ring<T, H>
 where
     T: Hash + Eq,
 {
@@ -262,7 +577,7 @@ where
     }
 }
 
-impl<'a, T, H> Default for Ring<'a, T, H>
+impl<T, H> Default for Ring<T, H>
 where
     T: Hash + Eq,
     H: BuildHasher + Default,
@@ -272,6 +587,56 @@ where
     }
 }
 
+/// Serializes the ids and replica counts of the nodes in the ring. The hash builder is not
+/// serialized.
+#[cfg(feature = "serde")]
+impl<T, H> Serialize for Ring<T, H>
+where
+    T: Serialize + Hash + Eq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T> {
+            nodes: Vec<(&'a T, usize)>,
+        }
+
+        State {
+            nodes: self.iter().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a ring from the ids and replica counts of its nodes. The ring is rebuilt with
+/// `H::default()`, so lookups are only preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, H> Deserialize<'de> for Ring<T, H>
+where
+    T: Deserialize<'de> + Hash + Eq,
+    H: BuildHasher + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T> {
+            nodes: Vec<(T, usize)>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut ring = Self::default();
+        for (id, weight) in state.nodes {
+            ring.try_insert_node(id, weight)
+                .map_err(de::Error::custom)?;
+        }
+        Ok(ring)
+    }
+}
+
 /// A client that uses `Ring<T>`.
 ///
 /// # Examples
@@ -279,9 +644,9 @@ where
 /// use hash_rings::rendezvous::Client;
 ///
 /// let mut client = Client::new();
-/// client.insert_node(&"node-1", 3);
-/// client.insert_point(&"point-1");
-/// client.insert_point(&"point-2");
+/// client.insert_node("node-1", 3);
+/// client.insert_point("point-1");
+/// client.insert_point("point-2");
 ///
 /// assert_eq!(client.len(), 1);
 /// assert_eq!(client.get_node(&"point-1"), &"node-1");
@@ -289,14 +654,14 @@ where
 /// client.remove_point(&"point-2");
 /// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
 /// ```
-pub struct Client<'a, T, U, H = RandomState> {
-    ring: Ring<'a, T, H>,
-    nodes: HashMap<&'a T, HashSet<&'a U>>,
-    points: HashMap<&'a U, (&'a T, u64)>,
+pub struct Client<T, U, H = StableBuildHasher> {
+    ring: Ring<T, H>,
+    nodes: HashMap<T, HashSet<U>>,
+    points: HashMap<U, (T, u64)>,
     hash_builder: H,
 }
 
-impl<'a, T, U> Client<'a, T, U, RandomState> {
+impl<T, U> Client<T, U, StableBuildHasher> {
     /// Constructs a new, empty `Client<T, U>`.
     ///
     /// # Examples
@@ -315,7 +680,7 @@ impl<'a, T, U> Client<'a, T, U, RandomState> {
     }
 }
 
-impl<'a, T, U, H> Client<'a, T, U, H> {
+impl<T, U, H> Client<T, U, H> {
     /// Constructs a new, empty `Client<T, U>` with a specified hash builder.
     ///
     /// # Examples
@@ -343,7 +708,8 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
         }
     }
 
-    /// Inserts a node into the ring with a number of replicas.
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
     ///
     /// Increasing the number of replicas will increase the number of expected points mapped to the
     /// node. For example, a node with three replicas will receive approximately three times more
@@ -357,19 +723,23 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// let mut client: Client<&str, &str> = Client::new();
     ///
     /// // "node-2" will receive three times more points than "node-1"
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_node(&"node-2", 3);
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.insert_node("node-2", 3) {
+    ///     assert_eq!((point, from, to), ("point-1", "node-1", "node-2"));
+    /// }
     /// ```
-    pub fn insert_node(&mut self, id: &'a T, replicas: usize)
+    pub fn insert_node(&mut self, id: T, replicas: usize) -> Vec<(U, T, T)>
     where
-        T: Hash + Eq,
-        U: Hash + Eq,
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        self.ring.insert_node(id, replicas);
-        let hashes = self.ring.get_hashes(id);
+        self.ring.insert_node(id.clone(), replicas);
+        let hashes = self.ring.get_hashes(&id);
 
         let mut new_points = HashSet::new();
+        let mut migrations = Vec::new();
 
         let Client {
             ref mut points,
@@ -391,16 +761,49 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
                     .get_mut(original_node)
                     .expect("Expected node to exist.")
                     .remove(point);
-                new_points.insert(*point);
+                new_points.insert(point.clone());
+                if *original_node != id {
+                    migrations.push((point.clone(), original_node.clone(), id.clone()));
+                }
                 *original_score = max_score;
-                *original_node = id;
+                *original_node = id.clone();
             }
         }
 
         self.nodes.insert(id, new_points);
+        migrations
     }
 
-    /// Removes a node and all its replicas from the ring.
+    /// Inserts a node into the ring with a number of replicas and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
+    ///
+    /// Returns `Error::InvalidWeight` if the number of replicas is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// assert_eq!(client.try_insert_node("node-1", 1), Ok(vec![]));
+    /// assert_eq!(client.try_insert_node("node-2", 0), Err(Error::InvalidWeight));
+    /// ```
+    pub fn try_insert_node(&mut self, id: T, replicas: usize) -> Result<Vec<(U, T, T)>, Error>
+    where
+        T: Hash + Eq + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if replicas == 0 {
+            return Err(Error::InvalidWeight);
+        }
+        Ok(self.insert_node(id, replicas))
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
     ///
     /// # Panics
     ///
@@ -413,38 +816,84 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_node(&"node-2", 1);
-    /// client.remove_node(&"node-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// client.insert_point("point-1");
+    /// for (point, from, to) in client.remove_node(&"node-1") {
+    ///     assert_eq!((point, from, to), ("point-1", "node-1", "node-2"));
+    /// }
     /// ```
-    pub fn remove_node(&mut self, id: &T)
+    pub fn remove_node(&mut self, id: &T) -> Vec<(U, T, T)>
     where
-        T: Hash + Ord,
-        U: Hash + Eq,
+        T: Hash + Ord + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        self.ring.remove_node(id);
-        if self.ring.is_empty() {
-            panic!("Error: empty ring after deletion.");
+        match self.try_remove_node(id) {
+            Ok(migrations) => migrations,
+            Err(err) => panic!("Error: {}.", err),
         }
-        if let Some(points) = self.nodes.remove(id) {
-            for point in points {
-                let new_node = self.ring.get_node(point);
-                let hashes = self.ring.get_hashes(new_node);
-                let point_hash = util::gen_hash(&self.hash_builder, point);
-                let max_score = hashes
-                    .iter()
-                    .map(|hash| util::combine_hash(&self.hash_builder, *hash, point_hash))
-                    .max()
-                    .expect("Expected non-zero number of replicas");
-
-                self.nodes
-                    .get_mut(new_node)
-                    .expect("Expected node to exist.")
-                    .insert(point);
-                self.points.insert(point, (new_node, max_score));
-            }
+    }
+
+    /// Removes a node and all its replicas from the ring and returns the points that moved as
+    /// `(point, from, to)` tuples in no particular order.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist and `Error::EmptyRing` if the ring
+    /// would be empty after removal of the node. The client is left unchanged if an error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_node("node-2", 1);
+    /// assert_eq!(client.try_remove_node(&"node-3"), Err(Error::UnknownNode));
+    /// assert_eq!(client.try_remove_node(&"node-2"), Ok(vec![]));
+    /// assert_eq!(client.try_remove_node(&"node-1"), Err(Error::EmptyRing));
+    /// ```
+    pub fn try_remove_node(&mut self, id: &T) -> Result<Vec<(U, T, T)>, Error>
+    where
+        T: Hash + Ord + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        if !self.nodes.contains_key(id) {
+            return Err(Error::UnknownNode);
+        }
+        if self
+            .ring
+            .nodes
+            .iter()
+            .all(|(other_id, hashes)| other_id == id || hashes.is_empty())
+        {
+            return Err(Error::EmptyRing);
         }
+        self.ring.remove_node(id);
+        let points = self.nodes.remove(id).expect("Expected node to exist.");
+        let mut migrations = Vec::with_capacity(points.len());
+        for point in points {
+            let new_node = self.ring.get_node(&point);
+            let point_hash = util::gen_hash(&self.hash_builder, &point);
+            let max_score = self.ring.nodes[new_node]
+                .iter()
+                .map(|hash| util::combine_hash(&self.hash_builder, *hash, point_hash))
+                .max()
+                .expect("Expected non-zero number of replicas");
+
+            self.points
+                .insert(point.clone(), (new_node.clone(), max_score));
+            self.nodes
+                .get_mut(new_node)
+                .expect("Expected node to exist.")
+                .insert(point.clone());
+            migrations.push((point, id.clone(), new_node.clone()));
+        }
+        Ok(migrations)
     }
 
     /// Returns the points associated with a node and its replicas.
@@ -460,8 +909,8 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// assert_eq!(client.get_points(&"node-1"), [&"point-1"]);
     /// ```
     pub fn get_points(&self, id: &T) -> Vec<&U>
@@ -469,7 +918,38 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
         T: Hash + Eq,
         U: Hash + Eq,
     {
-        self.nodes[id].iter().cloned().collect()
+        match self.try_get_points(id) {
+            Ok(points) => points,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Returns the points associated with a node and its replicas.
+    ///
+    /// Returns `Error::UnknownNode` if the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_get_points(&"node-1"), Ok(vec![&"point-1"]));
+    /// assert_eq!(client.try_get_points(&"node-2"), Err(Error::UnknownNode));
+    /// ```
+    pub fn try_get_points(&self, id: &T) -> Result<Vec<&U>, Error>
+    where
+        T: Hash + Eq,
+        U: Hash + Eq,
+    {
+        self.nodes
+            .get(id)
+            .map(|points| points.iter().collect())
+            .ok_or(Error::UnknownNode)
     }
 
     /// Returns the node associated with a point.
@@ -485,8 +965,8 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// assert_eq!(client.get_node(&"point-1"), &"node-1");
     /// ```
     pub fn get_node(&self, point: &U) -> &T
@@ -498,6 +978,53 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
         self.ring.get_node(point)
     }
 
+    /// Returns the node associated with a point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    /// assert_eq!(client.try_get_node(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_get_node(&"point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_get_node(&self, point: &U) -> Result<&T, Error>
+    where
+        T: Hash + Ord,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.try_get_node(point)
+    }
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference. The first
+    /// node is the node returned by `get_node`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.get_nodes(&"point-1", 2), [&"node-1"]);
+    /// ```
+    pub fn get_nodes(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        T: Hash + Ord,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        self.ring.get_nodes(point, n)
+    }
+
     /// Inserts a point into the ring and returns the node associated with the inserted point.
     ///
     /// # Panics
@@ -510,29 +1037,56 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// use hash_rings::rendezvous::Client;
     ///
     /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// ```
-    pub fn insert_point(&mut self, point: &'a U) -> &T
+    pub fn insert_point(&mut self, point: U) -> &T
     where
-        T: Hash + Ord,
-        U: Hash + Eq,
+        T: Hash + Ord + Clone,
+        U: Hash + Eq + Clone,
         H: BuildHasher,
     {
-        let node = self.ring.get_node(point);
-        let hashes = self.ring.get_hashes(node);
-        let point_hash = util::gen_hash(&self.hash_builder, point);
-        let max_score = hashes
+        match self.try_insert_point(point) {
+            Ok(node) => node,
+            Err(err) => panic!("Error: {}.", err),
+        }
+    }
+
+    /// Inserts a point into the ring and returns the node associated with the inserted point.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client = Client::new();
+    /// assert_eq!(client.try_insert_point("point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// assert_eq!(client.try_insert_point("point-1"), Ok(&"node-1"));
+    /// ```
+    pub fn try_insert_point(&mut self, point: U) -> Result<&T, Error>
+    where
+        T: Hash + Ord + Clone,
+        U: Hash + Eq + Clone,
+        H: BuildHasher,
+    {
+        let node = self.ring.try_get_node(&point)?;
+        let point_hash = util::gen_hash(&self.hash_builder, &point);
+        let max_score = self.ring.nodes[node]
             .iter()
             .map(|hash| util::combine_hash(&self.hash_builder, *hash, point_hash))
             .max()
             .expect("Expected non-zero number of replicas.");
+        self.points.insert(point.clone(), (node.clone(), max_score));
         self.nodes
             .get_mut(node)
             .expect("Expected node to exist.")
             .insert(point);
-        self.points.insert(point, (node, max_score));
-        node
+        Ok(node)
     }
 
     /// Removes a point from the ring.
@@ -547,8 +1101,8 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// use hash_rings::rendezvous::Client;
     ///
     /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     /// client.remove_point(&"point-1");
     /// ```
     pub fn remove_point(&mut self, point: &U)
@@ -557,12 +1111,41 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
         U: Hash + Eq,
         H: BuildHasher,
     {
-        let node = self.ring.get_node(point);
+        if let Err(err) = self.try_remove_point(point) {
+            panic!("Error: {}.", err);
+        }
+    }
+
+    /// Removes a point from the ring.
+    ///
+    /// Returns `Error::EmptyRing` if the ring is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::rendezvous::Client;
+    /// use hash_rings::Error;
+    ///
+    /// let mut client: Client<&str, &str> = Client::new();
+    /// assert_eq!(client.try_remove_point(&"point-1"), Err(Error::EmptyRing));
+    ///
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
+    /// assert_eq!(client.try_remove_point(&"point-1"), Ok(()));
+    /// ```
+    pub fn try_remove_point(&mut self, point: &U) -> Result<(), Error>
+    where
+        T: Hash + Ord,
+        U: Hash + Eq,
+        H: BuildHasher,
+    {
+        let node = self.ring.try_get_node(point)?;
         self.nodes
             .get_mut(node)
             .expect("Expected node to exist.")
             .remove(point);
         self.points.remove(point);
+        Ok(())
     }
 
     /// Returns the number of nodes in the ring.
@@ -574,7 +1157,7 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     ///
     /// let mut client: Client<&str, &str> = Client::new();
     ///
-    /// client.insert_node(&"node-1", 3);
+    /// client.insert_node("node-1", 3);
     /// assert_eq!(client.len(), 1);
     /// ```
     pub fn len(&self) -> usize
@@ -594,7 +1177,7 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// let mut client: Client<&str, &str> = Client::new();
     ///
     /// assert!(client.is_empty());
-    /// client.insert_node(&"node-1", 3);
+    /// client.insert_node("node-1", 3);
     /// assert!(!client.is_empty());
     /// ```
     pub fn is_empty(&self) -> bool
@@ -613,26 +1196,26 @@ impl<'a, T, U, H> Client<'a, T, U, H> {
     /// use hash_rings::rendezvous::Client;
     ///
     /// let mut client = Client::new();
-    /// client.insert_node(&"node-1", 1);
-    /// client.insert_point(&"point-1");
+    /// client.insert_node("node-1", 1);
+    /// client.insert_point("point-1");
     ///
     /// let mut iterator = client.iter();
     /// assert_eq!(iterator.next(), Some((&"node-1", vec![&"point-1"])));
     /// assert_eq!(iterator.next(), None);
     /// ```
-    pub fn iter(&'a self) -> impl Iterator<Item = (&'a T, Vec<&'a U>)>
+    pub fn iter(&self) -> impl Iterator<Item = (&T, Vec<&U>)>
     where
         T: Hash + Eq,
         U: Hash + Eq,
     {
         self.nodes.iter().map(move |node_entry| {
             let (node_id, points) = node_entry;
-            (&**node_id, points.iter().cloned().collect())
+            (node_id, points.iter().collect())
         })
     }
 }
 
-impl<'a, T, U, H> IntoIterator for &'a Client<'a, T, U, H>
+impl<'a, T, U, H> IntoIterator for &'a Client<T, U, H>
 where
     T: Hash + Eq,
     U: Hash + Eq,
@@ -645,7 +1228,7 @@ where
     }
 }
 
-impl<'a, T, U, H> Default for Client<'a, T, U, H>
+impl<T, U, H> Default for Client<T, U, H>
 where
     T: Hash + Eq,
     U: Hash + Eq,
@@ -656,14 +1239,75 @@ where
     }
 }
 
+/// Serializes the ids and replica counts of the nodes in the client and its points. The hash
+/// builder is not serialized.
+#[cfg(feature = "serde")]
+impl<T, U, H> Serialize for Client<T, U, H>
+where
+    T: Serialize + Hash + Eq,
+    U: Serialize + Hash + Eq,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct State<'a, T, U> {
+            nodes: Vec<(&'a T, usize)>,
+            points: Vec<&'a U>,
+        }
+
+        State {
+            nodes: self.ring.iter().collect(),
+            points: self.points.keys().collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserializes a client from the ids and replica counts of its nodes and its points. The client
+/// is rebuilt with `H::default()`, so lookups and the assignment of points to nodes are only
+/// preserved if the default hash builder is deterministic.
+#[cfg(feature = "serde")]
+impl<'de, T, U, H> Deserialize<'de> for Client<T, U, H>
+where
+    T: Deserialize<'de> + Hash + Ord + Clone,
+    U: Deserialize<'de> + Hash + Eq + Clone,
+    H: BuildHasher + Default + Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct State<T, U> {
+            nodes: Vec<(T, usize)>,
+            points: Vec<U>,
+        }
+
+        let state = State::deserialize(deserializer)?;
+        let mut client = Self::default();
+        for (id, weight) in state.nodes {
+            client
+                .try_insert_node(id, weight)
+                .map_err(de::Error::custom)?;
+        }
+        for point in state.points {
+            client.try_insert_point(point).map_err(de::Error::custom)?;
+        }
+        Ok(client)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Client, Ring};
-    use crate::test_util::BuildDefaultHasher;
+    use crate::test_util::{self, BuildDefaultHasher};
+    use crate::Error;
 
     #[test]
     fn test_size_empty() {
-        let client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         assert!(client.is_empty());
         assert_eq!(client.len(), 0);
     }
@@ -671,56 +1315,83 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_panic_remove_node_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 1);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 1);
         client.remove_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_remove_node_non_existent_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         client.remove_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_get_node_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         client.get_node(&0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_insert_point_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_point(0);
     }
 
     #[test]
     #[should_panic]
     fn test_panic_remove_point_empty_client() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
         client.remove_point(&0);
     }
 
+    #[test]
+    fn test_try_empty_client() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        assert_eq!(client.try_get_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_insert_point(0), Err(Error::EmptyRing));
+        assert_eq!(client.try_remove_point(&0), Err(Error::EmptyRing));
+        assert_eq!(client.try_get_points(&0), Err(Error::UnknownNode));
+        assert_eq!(client.try_insert_node(0, 0), Err(Error::InvalidWeight));
+        assert!(client.is_empty());
+    }
+
+    #[test]
+    fn test_try_remove_node() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        assert_eq!(client.try_remove_node(&0), Err(Error::UnknownNode));
+
+        client.insert_node(0, 1);
+        client.insert_point(0);
+        assert_eq!(client.try_remove_node(&0), Err(Error::EmptyRing));
+        assert_eq!(client.get_points(&0), [&0u32]);
+
+        client.insert_node(1, 1);
+        assert_eq!(client.try_remove_node(&0), Ok(vec![(0, 0, 1)]));
+        assert_eq!(client.try_get_points(&0), Err(Error::UnknownNode));
+        assert_eq!(client.get_points(&1), [&0u32]);
+    }
+
     #[test]
     fn test_insert_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&1, 1);
-        client.insert_point(&0);
-        client.insert_node(&0, 1);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(1, 1);
+        client.insert_point(0);
+        client.insert_node(0, 1);
         assert_eq!(client.get_points(&0), [&0u32]);
     }
 
     #[test]
     fn test_remove_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 1);
-        client.insert_point(&0);
-        client.insert_point(&1);
-        client.insert_point(&2);
-        client.insert_node(&1, 1);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 1);
+        client.insert_point(0);
+        client.insert_point(1);
+        client.insert_point(2);
+        client.insert_node(1, 1);
         client.remove_node(&1);
 
         let points = client.get_points(&0);
@@ -732,24 +1403,49 @@ mod tests {
 
     #[test]
     fn test_get_node() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
         assert_eq!(client.get_node(&0), &0);
     }
 
+    #[test]
+    fn test_migrations() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 10);
+        client.insert_node(1, 10);
+        for point in 0..1000 {
+            client.insert_point(point);
+        }
+        let get_nodes = |client: &Client<u32, u32, BuildDefaultHasher>| {
+            (0..1000)
+                .map(|point| (point, *client.get_node(&point)))
+                .collect::<Vec<(u32, u32)>>()
+        };
+
+        let before = get_nodes(&client);
+        let migrations = client.insert_node(2, 10);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+
+        let before = get_nodes(&client);
+        let migrations = client.remove_node(&0);
+        assert!(!migrations.is_empty());
+        test_util::assert_migrations(&before, &get_nodes(&client), migrations);
+    }
+
     #[test]
     fn test_insert_point() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(0);
         assert_eq!(client.get_points(&0), [&0u32]);
     }
 
     #[test]
     fn test_remove_point() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&0);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(0);
         client.remove_point(&0);
         let expected: [&u32; 0] = [];
         assert_eq!(client.get_points(&0), expected);
@@ -757,13 +1453,13 @@ mod tests {
 
     #[test]
     fn test_iter() {
-        let mut client: Client<'_, u32, u32, BuildDefaultHasher> = Client::default();
-        client.insert_node(&0, 3);
-        client.insert_point(&1);
-        client.insert_point(&2);
-        client.insert_point(&3);
-        client.insert_point(&4);
-        client.insert_point(&5);
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_point(1);
+        client.insert_point(2);
+        client.insert_point(3);
+        client.insert_point(4);
+        client.insert_point(5);
         let mut actual: Vec<(&u32, Vec<&u32>)> = client.iter().collect();
         actual[0].1.sort();
         assert_eq!(actual[0].0, &0);
@@ -774,17 +1470,112 @@ mod tests {
     fn test_ring_len() {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default());
 
-        ring.insert_node(&0, 1);
+        ring.insert_node(0, 1);
         assert_eq!(ring.len(), 1);
     }
 
+    #[test]
+    fn test_ring_get_nodes_batch() {
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 1);
+        ring.insert_node(1, 3);
+        ring.insert_node(2, 0);
+        ring.insert_node(3, 2);
+
+        let points: Vec<u32> = (0..1000).collect();
+        let nodes = ring.get_nodes_batch(&points);
+        assert_eq!(nodes.len(), points.len());
+        for (point, node) in points.iter().zip(nodes) {
+            assert_eq!(node, ring.get_node(point));
+        }
+        assert_eq!(ring.get_nodes_batch::<u32>(&[]), Vec::<&u32>::new());
+    }
+
+    #[test]
+    fn test_ring_get_nodes_batch_prefixed() {
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 1);
+        ring.insert_node(1, 3);
+        ring.insert_node(2, 0);
+        ring.insert_node(3, 2);
+
+        let points: Vec<u32> = (0..1000).collect();
+        assert_eq!(
+            ring.get_nodes_batch_prefixed(&points),
+            ring.get_nodes_batch(&points)
+        );
+        assert_eq!(
+            ring.get_nodes_batch_prefixed::<u32>(&[]),
+            Vec::<&u32>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_ring_get_nodes_batch_prefixed_empty_ring() {
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 0);
+        ring.get_nodes_batch_prefixed(&[0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_ring_get_nodes_batch_empty_ring() {
+        let mut ring: Ring<u32, BuildDefaultHasher> = Ring::default();
+        ring.insert_node(0, 0);
+        ring.get_nodes_batch(&[0]);
+    }
+
     #[test]
     fn test_ring_iter() {
         let mut ring = Ring::with_hasher(BuildDefaultHasher::default());
 
-        ring.insert_node(&0, 1);
+        ring.insert_node(0, 1);
         let mut iterator = ring.iter();
         assert_eq!(iterator.next(), Some((&0, 1)));
         assert_eq!(iterator.next(), None);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let mut client: Client<u32, u32, BuildDefaultHasher> = Client::default();
+        client.insert_node(0, 3);
+        client.insert_node(1, 2);
+        client.insert_node(2, 1);
+        for point in 0..100 {
+            client.insert_point(point);
+        }
+
+        let json = serde_json::to_string(&client).unwrap();
+        let other: Client<u32, u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(other.len(), 3);
+        for node in 0..3 {
+            let mut expected = client.get_points(&node);
+            let mut actual = other.get_points(&node);
+            expected.sort();
+            actual.sort();
+            assert_eq!(expected, actual);
+        }
+        for point in 0..200 {
+            assert_eq!(client.get_node(&point), other.get_node(&point));
+        }
+
+        let json = serde_json::to_string(&client.ring).unwrap();
+        let ring: Ring<u32, BuildDefaultHasher> = serde_json::from_str(&json).unwrap();
+        for point in 0..200 {
+            assert_eq!(client.get_nodes(&point, 3), ring.get_nodes(&point, 3));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_invalid_client() {
+        let json = r#"{"nodes":[[0,0]],"points":[]}"#;
+        assert!(serde_json::from_str::<Client<u32, u32, BuildDefaultHasher>>(json).is_err());
+
+        let json = r#"{"nodes":[],"points":[0]}"#;
+        assert!(serde_json::from_str::<Client<u32, u32, BuildDefaultHasher>>(json).is_err());
+    }
 }
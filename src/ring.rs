@@ -0,0 +1,955 @@
+//! Traits shared by all hashing rings.
+
+use crate::{carp, consistent, jump, maglev, memento, mpc, rendezvous, weighted_rendezvous, Error};
+use std::hash::{BuildHasher, Hash};
+
+/// A hashing ring that maps points to nodes.
+///
+/// Every ring in this crate except `ketama::Ring` and `maglev::EnvoyRing`, which hash the bytes of
+/// points, implements `HashRing`, so code that routes points to nodes can be written once and be
+/// generic over the hashing algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::{consistent, mpc, HashRing};
+///
+/// fn route<'r, R: HashRing>(ring: &'r R, point: &str) -> R::Node<'r> {
+///     ring.get_node(&point)
+/// }
+///
+/// let mut consistent_ring: consistent::Ring<&str> = consistent::Ring::new();
+/// consistent_ring.insert_node("node-1", 1);
+///
+/// let mut mpc_ring: mpc::Ring<&str> = mpc::Ring::new(2);
+/// mpc_ring.insert_node("node-1", 1);
+///
+/// assert_eq!(route(&consistent_ring, "point-1"), &"node-1");
+/// assert_eq!(route(&mpc_ring, "point-1"), &"node-1");
+/// ```
+pub trait HashRing {
+    /// The type of node yielded by lookups and iteration.
+    type Node<'b>
+    where
+        Self: 'b;
+
+    /// Returns the node associated with a point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    fn get_node<U>(&self, point: &U) -> Self::Node<'_>
+    where
+        U: Hash;
+
+    /// Returns the node associated with a point, or `Error::EmptyRing` if the ring is empty.
+    fn try_get_node<U>(&self, point: &U) -> Result<Self::Node<'_>, Error>
+    where
+        U: Hash;
+
+    /// Returns up to `n` distinct nodes associated with a point in order of preference. The first
+    /// node is the node returned by `get_node`, and fewer than `n` nodes are returned if the ring
+    /// has fewer than `n` nodes.
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<Self::Node<'_>>
+    where
+        U: Hash;
+
+    /// Returns the node associated with a hash of a point. The hash of a point is the value
+    /// returned by `hash_one` on the ring's hash builder, so a point hashed once can be looked up
+    /// in several rings that share a hash builder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring is empty.
+    fn get_node_by_hash(&self, hash: u64) -> Self::Node<'_>;
+
+    /// Returns the node associated with a hash of a point, or `Error::EmptyRing` if the ring is
+    /// empty.
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<Self::Node<'_>, Error>;
+
+    /// Returns up to `n` distinct nodes associated with a hash of a point in order of preference.
+    /// The first node is the node returned by `get_node_by_hash`.
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<Self::Node<'_>>;
+
+    /// Returns the nodes associated with a batch of points in the order of the points.
+    ///
+    /// The default implementation looks up each point with `get_node`. Rings that can share work
+    /// between the points of a batch override it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is not empty and the ring is empty.
+    fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<Self::Node<'_>>
+    where
+        U: Hash,
+    {
+        points.iter().map(|point| self.get_node(point)).collect()
+    }
+
+    /// Returns the number of nodes in the ring.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the ring is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the nodes in the ring.
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = Self::Node<'_>> + '_>;
+}
+
+/// A hashing ring that supports inserting and removing nodes in place.
+///
+/// `maglev::Ring` and `jump::Ring` do not implement this trait because their lookup structures
+/// must be rebuilt from scratch when the set of nodes changes. `jump::NamedRing` does not implement
+/// it either since it can only append nodes and remove the last node.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::{rendezvous, weighted_rendezvous, MutableHashRing};
+///
+/// fn rotate<R: MutableHashRing>(ring: &mut R, old: R::Id, new: R::Id, weight: R::Weight) {
+///     ring.remove_node(&old);
+///     ring.insert_node(new, weight);
+/// }
+///
+/// let mut ring: rendezvous::Ring<&str> = rendezvous::Ring::new();
+/// ring.insert_node("node-1", 1);
+/// rotate(&mut ring, "node-1", "node-2", 1);
+/// assert_eq!(ring.get_node(&"point-1"), &"node-2");
+///
+/// let mut ring: weighted_rendezvous::Ring<&str> = weighted_rendezvous::Ring::new();
+/// ring.insert_node("node-1", 1f64);
+/// rotate(&mut ring, "node-1", "node-2", 1f64);
+/// assert_eq!(ring.get_node(&"point-1"), &"node-2");
+/// ```
+pub trait MutableHashRing: HashRing {
+    /// The type used to identify a node when inserting or removing it.
+    type Id;
+
+    /// The type used to weight a node, such as a replica count or a floating point weight.
+    type Weight;
+
+    /// Inserts a node into the ring with a particular weight.
+    fn insert_node(&mut self, id: Self::Id, weight: Self::Weight);
+
+    /// Removes a node from the ring.
+    fn remove_node(&mut self, id: &Self::Id);
+}
+
+impl<T, H> HashRing for carp::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        carp::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        carp::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        carp::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        carp::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        carp::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        carp::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        carp::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(carp::Ring::iter(self).map(|node| node.0))
+    }
+}
+
+impl<T, H> MutableHashRing for carp::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Id = T;
+    type Weight = f64;
+
+    fn insert_node(&mut self, id: T, weight: f64) {
+        carp::Ring::insert_node(self, carp::Node::new(id, weight))
+    }
+
+    fn remove_node(&mut self, id: &T) {
+        carp::Ring::remove_node(self, id)
+    }
+}
+
+impl<T, H> HashRing for consistent::Ring<T, H>
+where
+    T: Hash + Eq,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        consistent::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        consistent::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        consistent::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        consistent::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        consistent::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        consistent::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        consistent::Ring::get_nodes_batch(self, points)
+    }
+
+    fn len(&self) -> usize {
+        consistent::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(consistent::Ring::iter(self).map(|node| node.0))
+    }
+}
+
+impl<T, H> MutableHashRing for consistent::Ring<T, H>
+where
+    T: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    type Id = T;
+    type Weight = usize;
+
+    fn insert_node(&mut self, id: T, replicas: usize) {
+        consistent::Ring::insert_node(self, id, replicas)
+    }
+
+    fn remove_node(&mut self, id: &T) {
+        consistent::Ring::remove_node(self, id)
+    }
+}
+
+impl<T, H> HashRing for consistent::FrozenRing<T, H>
+where
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        consistent::FrozenRing::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        consistent::FrozenRing::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        consistent::FrozenRing::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        consistent::FrozenRing::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        consistent::FrozenRing::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        consistent::FrozenRing::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        consistent::FrozenRing::get_nodes_batch(self, points)
+    }
+
+    fn len(&self) -> usize {
+        consistent::FrozenRing::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(consistent::FrozenRing::iter(self).map(|node| node.0))
+    }
+}
+
+impl<H> HashRing for jump::Ring<H>
+where
+    H: BuildHasher,
+{
+    type Node<'b>
+        = u64
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> u64
+    where
+        U: Hash,
+    {
+        jump::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<u64, Error>
+    where
+        U: Hash,
+    {
+        Ok(jump::Ring::get_node(self, point))
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<u64>
+    where
+        U: Hash,
+    {
+        jump::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> u64 {
+        jump::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<u64, Error> {
+        Ok(jump::Ring::get_node_by_hash(self, hash))
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<u64> {
+        jump::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        self.nodes() as usize
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = u64> + '_> {
+        Box::new(0..self.nodes())
+    }
+}
+
+impl<T, H> HashRing for jump::NamedRing<T, H>
+where
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        jump::NamedRing::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        jump::NamedRing::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        jump::NamedRing::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        jump::NamedRing::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        jump::NamedRing::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        jump::NamedRing::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        jump::NamedRing::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(jump::NamedRing::iter(self))
+    }
+}
+
+impl<T, H> HashRing for maglev::Ring<T, H>
+where
+    T: Hash,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        maglev::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        Ok(maglev::Ring::get_node(self, point))
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        maglev::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        maglev::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        Ok(maglev::Ring::get_node_by_hash(self, hash))
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        maglev::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        self.nodes()
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(maglev::Ring::iter(self))
+    }
+}
+
+impl<H> HashRing for memento::Ring<H>
+where
+    H: BuildHasher,
+{
+    type Node<'b>
+        = u32
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> u32
+    where
+        U: Hash,
+    {
+        memento::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<u32, Error>
+    where
+        U: Hash,
+    {
+        memento::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<u32>
+    where
+        U: Hash,
+    {
+        memento::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> u32 {
+        memento::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<u32, Error> {
+        memento::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<u32> {
+        memento::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        memento::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        Box::new(memento::Ring::iter(self))
+    }
+}
+
+impl<T, H> HashRing for mpc::Ring<T, H>
+where
+    T: Hash + Eq,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        mpc::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        mpc::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        mpc::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        mpc::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        mpc::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        mpc::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn len(&self) -> usize {
+        mpc::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(mpc::Ring::iter(self).map(|node| node.0))
+    }
+}
+
+impl<T, H> MutableHashRing for mpc::Ring<T, H>
+where
+    T: Hash + Eq + Clone,
+    H: BuildHasher,
+{
+    type Id = T;
+    type Weight = usize;
+
+    fn insert_node(&mut self, id: T, replicas: usize) {
+        mpc::Ring::insert_node(self, id, replicas)
+    }
+
+    fn remove_node(&mut self, id: &T) {
+        mpc::Ring::remove_node(self, id)
+    }
+}
+
+impl<T, H> HashRing for rendezvous::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        rendezvous::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        rendezvous::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        rendezvous::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        rendezvous::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        rendezvous::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        rendezvous::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        rendezvous::Ring::get_nodes_batch(self, points)
+    }
+
+    fn len(&self) -> usize {
+        rendezvous::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(rendezvous::Ring::iter(self).map(|node| node.0))
+    }
+}
+
+impl<T, H> MutableHashRing for rendezvous::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Id = T;
+    type Weight = usize;
+
+    fn insert_node(&mut self, id: T, replicas: usize) {
+        rendezvous::Ring::insert_node(self, id, replicas)
+    }
+
+    fn remove_node(&mut self, id: &T) {
+        rendezvous::Ring::remove_node(self, id)
+    }
+}
+
+impl<T, H> HashRing for weighted_rendezvous::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Node<'b>
+        = &'b T
+    where
+        Self: 'b;
+
+    fn get_node<U>(&self, point: &U) -> &T
+    where
+        U: Hash,
+    {
+        weighted_rendezvous::Ring::get_node(self, point)
+    }
+
+    fn try_get_node<U>(&self, point: &U) -> Result<&T, Error>
+    where
+        U: Hash,
+    {
+        weighted_rendezvous::Ring::try_get_node(self, point)
+    }
+
+    fn get_nodes<U>(&self, point: &U, n: usize) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        weighted_rendezvous::Ring::get_nodes(self, point, n)
+    }
+
+    fn get_node_by_hash(&self, hash: u64) -> &T {
+        weighted_rendezvous::Ring::get_node_by_hash(self, hash)
+    }
+
+    fn try_get_node_by_hash(&self, hash: u64) -> Result<&T, Error> {
+        weighted_rendezvous::Ring::try_get_node_by_hash(self, hash)
+    }
+
+    fn get_nodes_by_hash(&self, hash: u64, n: usize) -> Vec<&T> {
+        weighted_rendezvous::Ring::get_nodes_by_hash(self, hash, n)
+    }
+
+    fn get_nodes_batch<U>(&self, points: &[U]) -> Vec<&T>
+    where
+        U: Hash,
+    {
+        weighted_rendezvous::Ring::get_nodes_batch(self, points)
+    }
+
+    fn len(&self) -> usize {
+        weighted_rendezvous::Ring::len(self)
+    }
+
+    fn iter_nodes(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(weighted_rendezvous::Ring::iter(self).map(|node| node.0))
+    }
+}
+
+impl<T, H> MutableHashRing for weighted_rendezvous::Ring<T, H>
+where
+    T: Hash + Ord,
+    H: BuildHasher,
+{
+    type Id = T;
+    type Weight = f64;
+
+    fn insert_node(&mut self, id: T, weight: f64) {
+        weighted_rendezvous::Ring::insert_node(self, id, weight)
+    }
+
+    fn remove_node(&mut self, id: &T) {
+        weighted_rendezvous::Ring::remove_node(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashRing, MutableHashRing};
+    use crate::test_util::{BuildAddHasher, BuildDefaultHasher};
+    use crate::{
+        carp, consistent, jump, maglev, memento, mpc, rendezvous, weighted_rendezvous, Error,
+    };
+    use std::cmp;
+    use std::hash::BuildHasher;
+
+    // Rings in these tests are constructed with `BuildDefaultHasher`, so lookups by hash use the
+    // same hash builder.
+    fn assert_routes_all<R>(ring: &R, len: usize)
+    where
+        R: HashRing,
+        for<'b> R::Node<'b>: PartialEq,
+    {
+        assert_eq!(ring.len(), len);
+        assert!(!ring.is_empty());
+        for point in 0..100u32 {
+            let node = ring.get_node(&point);
+            assert!(ring.try_get_node(&point) == Ok(ring.get_node(&point)));
+            assert!(ring.iter_nodes().any(|other| other == node));
+
+            let nodes = ring.get_nodes(&point, len + 1);
+            assert_eq!(nodes.len(), len);
+            assert!(nodes[0] == ring.get_node(&point));
+            for (i, node) in nodes.iter().enumerate() {
+                assert!(nodes[..i].iter().all(|other| other != node));
+            }
+            assert!(ring.get_nodes(&point, 2)[..] == nodes[..cmp::min(2, len)]);
+
+            let hash = BuildDefaultHasher::default().hash_one(point);
+            assert!(ring.get_node_by_hash(hash) == ring.get_node(&point));
+            assert!(ring.try_get_node_by_hash(hash) == Ok(ring.get_node(&point)));
+            assert!(ring.get_nodes_by_hash(hash, len + 1) == nodes);
+        }
+
+        let points: Vec<u32> = (0..1000).collect();
+        let nodes = ring.get_nodes_batch(&points);
+        assert_eq!(nodes.len(), points.len());
+        for (point, node) in points.iter().zip(nodes) {
+            assert!(node == ring.get_node(point));
+        }
+    }
+
+    fn assert_empty<R>(ring: &R)
+    where
+        R: HashRing,
+    {
+        assert!(ring.is_empty());
+        assert!(ring.try_get_node(&0u32).err() == Some(Error::EmptyRing));
+        assert!(ring.get_nodes(&0u32, 2).is_empty());
+        assert!(ring.try_get_node_by_hash(0).err() == Some(Error::EmptyRing));
+        assert!(ring.get_nodes_by_hash(0, 2).is_empty());
+        assert!(ring.get_nodes_batch::<u32>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_carp() {
+        let mut ring = carp::Ring::with_hasher(BuildDefaultHasher::default(), vec![]);
+        MutableHashRing::insert_node(&mut ring, 0, 1f64);
+        MutableHashRing::insert_node(&mut ring, 1, 2f64);
+        assert_routes_all(&ring, 2);
+
+        MutableHashRing::remove_node(&mut ring, &0);
+        assert_routes_all(&ring, 1);
+
+        MutableHashRing::remove_node(&mut ring, &1);
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_consistent() {
+        let mut ring = consistent::Ring::with_hasher(BuildDefaultHasher::default());
+        MutableHashRing::insert_node(&mut ring, 0, 1);
+        MutableHashRing::insert_node(&mut ring, 1, 3);
+        assert_routes_all(&ring, 2);
+
+        MutableHashRing::remove_node(&mut ring, &0);
+        assert_routes_all(&ring, 1);
+
+        MutableHashRing::remove_node(&mut ring, &1);
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_consistent_frozen() {
+        let mut ring = consistent::Ring::with_hasher(BuildDefaultHasher::default());
+        ring.insert_node(0, 1);
+        ring.insert_node(1, 3);
+        assert_routes_all(&ring.freeze(), 2);
+
+        let ring: consistent::Ring<u32, _> =
+            consistent::Ring::with_hasher(BuildDefaultHasher::default());
+        assert_empty(&ring.freeze());
+    }
+
+    #[test]
+    fn test_jump() {
+        let ring = jump::Ring::with_hasher(BuildDefaultHasher::default(), 10);
+        assert_routes_all(&ring, 10);
+        assert_eq!(
+            ring.iter_nodes().collect::<Vec<_>>(),
+            (0..10).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_jump_named() {
+        let mut ring = jump::NamedRing::with_hasher(BuildDefaultHasher::default(), vec![0, 1, 2]);
+        assert_routes_all(&ring, 3);
+
+        ring.pop_node();
+        ring.pop_node();
+        ring.pop_node();
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_memento() {
+        let mut ring = memento::Ring::with_hasher(BuildDefaultHasher::default(), 3);
+        ring.remove_node(1);
+        assert_routes_all(&ring, 2);
+
+        ring.remove_node(0);
+        ring.remove_node(2);
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_maglev() {
+        let ring = maglev::Ring::with_hasher(BuildDefaultHasher::default(), vec![0, 1, 2], 300);
+        assert_routes_all(&ring, 3);
+    }
+
+    #[test]
+    fn test_mpc() {
+        let mut ring = mpc::Ring::with_hasher(BuildDefaultHasher::default(), 2);
+        MutableHashRing::insert_node(&mut ring, 0, 1);
+        MutableHashRing::insert_node(&mut ring, 1, 2);
+        assert_routes_all(&ring, 2);
+
+        MutableHashRing::remove_node(&mut ring, &0);
+        assert_routes_all(&ring, 1);
+
+        MutableHashRing::remove_node(&mut ring, &1);
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_rendezvous() {
+        let mut ring = rendezvous::Ring::with_hasher(BuildDefaultHasher::default());
+        MutableHashRing::insert_node(&mut ring, 0, 1);
+        MutableHashRing::insert_node(&mut ring, 1, 3);
+        assert_routes_all(&ring, 2);
+
+        MutableHashRing::remove_node(&mut ring, &0);
+        assert_routes_all(&ring, 1);
+
+        MutableHashRing::remove_node(&mut ring, &1);
+        assert_empty(&ring);
+    }
+
+    #[test]
+    fn test_rendezvous_hasher_without_clone() {
+        // `AddHasher` does not implement `Clone`.
+        let mut ring = rendezvous::Ring::with_hasher(BuildAddHasher::default());
+        MutableHashRing::insert_node(&mut ring, 0, 1);
+        assert_eq!(HashRing::get_nodes_batch(&ring, &[0u32, 1]), [&0, &0]);
+    }
+
+    #[test]
+    fn test_weighted_rendezvous() {
+        let mut ring = weighted_rendezvous::Ring::with_hasher(BuildDefaultHasher::default());
+        MutableHashRing::insert_node(&mut ring, 0, 1f64);
+        MutableHashRing::insert_node(&mut ring, 1, 3f64);
+        assert_routes_all(&ring, 2);
+
+        MutableHashRing::remove_node(&mut ring, &0);
+        assert_routes_all(&ring, 1);
+
+        MutableHashRing::remove_node(&mut ring, &1);
+        assert_empty(&ring);
+    }
+}
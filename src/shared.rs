@@ -0,0 +1,345 @@
+//! Lock-free sharing of rings between threads.
+
+use crate::MutableHashRing;
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+/// A ring shared between threads that publishes immutable snapshots of the ring.
+///
+/// Readers load the current snapshot of the ring without taking a lock, so they never block and
+/// are never blocked by writers. Writers clone the current snapshot, change the clone, and publish
+/// it atomically, so readers either observe all of the changes of an update or none of them. A
+/// snapshot remains valid after newer snapshots are published, so readers that need consistent
+/// answers across several lookups should load one snapshot and use it for all of them.
+///
+/// `SharedRing` works with every ring in this crate. Rings that implement `MutableHashRing` can
+/// be updated with `insert_node` and `remove_node`, and other rings can be updated with `update`
+/// or replaced with `store`.
+///
+/// # Examples
+///
+/// ```
+/// use hash_rings::consistent::Ring;
+/// use hash_rings::SharedRing;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let ring: Arc<SharedRing<Ring<&str>>> = Arc::new(SharedRing::new(Ring::new()));
+/// ring.insert_node("node-1", 1);
+///
+/// let reader = {
+///     let ring = Arc::clone(&ring);
+///     thread::spawn(move || ring.read(|ring| ring.try_get_node(&"point-1").is_ok()))
+/// };
+/// ring.insert_node("node-2", 3);
+///
+/// assert!(reader.join().unwrap());
+/// assert_eq!(ring.load().len(), 2);
+/// ```
+pub struct SharedRing<R> {
+    ring: ArcSwap<R>,
+}
+
+impl<R> SharedRing<R> {
+    /// Constructs a new `SharedRing<R>` whose first snapshot is `ring`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    /// ```
+    pub fn new(ring: R) -> Self {
+        SharedRing {
+            ring: ArcSwap::from_pointee(ring),
+        }
+    }
+
+    /// Returns the current snapshot of the ring. The snapshot is not affected by later updates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    /// let snapshot = ring.load();
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert!(snapshot.is_empty());
+    /// assert_eq!(ring.load().len(), 1);
+    /// ```
+    pub fn load(&self) -> Arc<R> {
+        self.ring.load_full()
+    }
+
+    /// Calls `f` with the current snapshot of the ring and returns its result. Unlike `load`, this
+    /// does not touch the reference count of the snapshot, so it is the cheaper way to perform
+    /// short lookups from many threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    ///
+    /// ring.insert_node("node-1", 1);
+    /// assert_eq!(ring.read(|ring| *ring.get_node(&"point-1")), "node-1");
+    /// ```
+    pub fn read<F, O>(&self, f: F) -> O
+    where
+        F: FnOnce(&R) -> O,
+    {
+        f(&self.ring.load())
+    }
+
+    /// Publishes `ring` as the new snapshot of the ring and returns the previous snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::maglev::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring = SharedRing::new(Ring::new(vec!["node-1"]));
+    /// let previous = ring.store(Ring::new(vec!["node-1", "node-2"]));
+    ///
+    /// assert_eq!(previous.nodes(), 1);
+    /// assert_eq!(ring.load().nodes(), 2);
+    /// ```
+    pub fn store(&self, ring: R) -> Arc<R> {
+        self.ring.swap(Arc::new(ring))
+    }
+
+    /// Publishes a new snapshot of the ring built by calling `f` on a clone of the current
+    /// snapshot, and returns the result of `f`.
+    ///
+    /// If another writer publishes a snapshot while `f` is running, the new snapshot is discarded
+    /// and `f` is called again on a clone of the other writer's snapshot, so no update is lost.
+    /// `f` should therefore not have side effects other than changing the ring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::consistent::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    ///
+    /// ring.update(|ring| {
+    ///     ring.insert_node("node-1", 1);
+    ///     ring.insert_node("node-2", 1);
+    /// });
+    /// assert_eq!(ring.load().len(), 2);
+    /// ```
+    pub fn update<F, O>(&self, mut f: F) -> O
+    where
+        R: Clone,
+        F: FnMut(&mut R) -> O,
+    {
+        let mut current = self.ring.load();
+        loop {
+            let mut ring = R::clone(&current);
+            let ret = f(&mut ring);
+            let previous = self.ring.compare_and_swap(&*current, Arc::new(ring));
+            if Arc::ptr_eq(&previous, &current) {
+                return ret;
+            }
+            current = previous;
+        }
+    }
+
+    /// Publishes a new snapshot of the ring with a node inserted with a particular weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::weighted_rendezvous::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    ///
+    /// ring.insert_node("node-1", 1f64);
+    /// assert_eq!(ring.load().len(), 1);
+    /// ```
+    pub fn insert_node(&self, id: R::Id, weight: R::Weight)
+    where
+        R: MutableHashRing + Clone,
+        R::Id: Clone,
+        R::Weight: Clone,
+    {
+        self.update(|ring| ring.insert_node(id.clone(), weight.clone()))
+    }
+
+    /// Publishes a new snapshot of the ring with a node removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ring panics when removing the node, such as when the node does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use hash_rings::weighted_rendezvous::Ring;
+    /// use hash_rings::SharedRing;
+    ///
+    /// let ring: SharedRing<Ring<&str>> = SharedRing::new(Ring::new());
+    ///
+    /// ring.insert_node("node-1", 1f64);
+    /// ring.remove_node(&"node-1");
+    /// assert!(ring.load().is_empty());
+    /// ```
+    pub fn remove_node(&self, id: &R::Id)
+    where
+        R: MutableHashRing + Clone,
+    {
+        self.update(|ring| ring.remove_node(id))
+    }
+}
+
+impl<R> Default for SharedRing<R>
+where
+    R: Default,
+{
+    fn default() -> Self {
+        SharedRing::new(R::default())
+    }
+}
+
+impl<R> From<R> for SharedRing<R> {
+    fn from(ring: R) -> Self {
+        SharedRing::new(ring)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SharedRing;
+    use crate::test_util::BuildDefaultHasher;
+    use crate::{
+        carp, consistent, maglev, memento, mpc, rendezvous, weighted_rendezvous, MutableHashRing,
+    };
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    const WRITERS: u32 = 4;
+    const READERS: u32 = 4;
+    const UPDATES: u32 = 50;
+
+    // Runs writers that concurrently insert and remove pairs of nodes in single updates alongside
+    // readers that assert that they never observe half of an update, and asserts that no update
+    // is lost.
+    fn assert_concurrent_updates<R>(ring: R, weight: R::Weight)
+    where
+        R: MutableHashRing<Id = u32> + Clone + Send + Sync,
+        R::Weight: Clone + Send + Sync,
+    {
+        let ring = SharedRing::new(ring);
+        let done = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            let readers: Vec<_> = (0..READERS)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut reads = 0;
+                        while reads == 0 || !done.load(Ordering::Acquire) {
+                            let snapshot = ring.load();
+                            assert_eq!(snapshot.len() % 2, 0);
+                            assert_eq!(snapshot.iter_nodes().count(), snapshot.len());
+                            for point in 0..10u32 {
+                                assert_eq!(
+                                    snapshot.try_get_node(&point).is_ok(),
+                                    !snapshot.is_empty()
+                                );
+                            }
+                            ring.read(|ring| assert_eq!(ring.len() % 2, 0));
+                            reads += 1;
+                        }
+                    })
+                })
+                .collect();
+
+            let writers: Vec<_> = (0..WRITERS)
+                .map(|writer| {
+                    let ring = &ring;
+                    let weight = weight.clone();
+                    scope.spawn(move || {
+                        for update in 0..UPDATES {
+                            let id = 2 * (writer * UPDATES + update);
+                            ring.update(|ring| {
+                                ring.insert_node(id, weight.clone());
+                                ring.insert_node(id + 1, weight.clone());
+                            });
+                            if update % 2 == 1 {
+                                ring.update(|ring| {
+                                    ring.remove_node(&id);
+                                    ring.remove_node(&(id + 1));
+                                });
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            for writer in writers {
+                writer.join().unwrap();
+            }
+            done.store(true, Ordering::Release);
+            for reader in readers {
+                reader.join().unwrap();
+            }
+        });
+
+        assert_eq!(ring.load().len(), (WRITERS * UPDATES) as usize);
+    }
+
+    #[test]
+    fn test_concurrent_updates() {
+        let hash_builder = BuildDefaultHasher::default();
+        assert_concurrent_updates(carp::Ring::with_hasher(hash_builder.clone(), vec![]), 1f64);
+        assert_concurrent_updates(consistent::Ring::with_hasher(hash_builder.clone()), 3);
+        assert_concurrent_updates(mpc::Ring::with_hasher(hash_builder.clone(), 2), 1);
+        assert_concurrent_updates(rendezvous::Ring::with_hasher(hash_builder.clone()), 1);
+        assert_concurrent_updates(weighted_rendezvous::Ring::with_hasher(hash_builder), 1f64);
+    }
+
+    #[test]
+    fn test_load_is_snapshot() {
+        let ring: SharedRing<consistent::Ring<u32>> = SharedRing::default();
+        ring.insert_node(0, 1);
+        let snapshot = ring.load();
+
+        ring.insert_node(1, 1);
+        ring.remove_node(&0);
+        assert_eq!(snapshot.iter().collect::<Vec<_>>(), [(&0, 1)]);
+        assert_eq!(ring.load().iter().collect::<Vec<_>>(), [(&1, 1)]);
+    }
+
+    #[test]
+    fn test_update_returns_result() {
+        let ring = SharedRing::from(memento::Ring::new(3));
+        assert_eq!(ring.update(|ring| ring.try_remove_node(1)), Ok(()));
+        assert!(ring.update(|ring| ring.try_remove_node(1)).is_err());
+        assert_eq!(ring.load().len(), 2);
+    }
+
+    #[test]
+    fn test_store() {
+        let ring = SharedRing::new(maglev::Ring::new(vec![0, 1]));
+        let previous = ring.store(maglev::Ring::new(vec![0, 1, 2]));
+        assert_eq!(previous.nodes(), 2);
+        assert_eq!(ring.load().nodes(), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panic_remove_node_non_existent_node() {
+        let ring: SharedRing<consistent::Ring<u32>> = SharedRing::default();
+        ring.remove_node(&0);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
 use std::hash::{BuildHasherDefault, Hasher};
 
+#[derive(Default)]
 pub struct AddHasher {
     sum: u64,
 }
@@ -17,11 +19,23 @@ impl Hasher for AddHasher {
     }
 }
 
-impl Default for AddHasher {
-    fn default() -> Self {
-        Self { sum: 0 }
-    }
-}
-
 pub type BuildAddHasher = BuildHasherDefault<AddHasher>;
 pub type BuildDefaultHasher = BuildHasherDefault<DefaultHasher>;
+
+// Asserts that `migrations` contains exactly the points whose node changed from `before` to
+// `after`.
+pub fn assert_migrations<T, U>(before: &[(U, T)], after: &[(U, T)], mut migrations: Vec<(U, T, T)>)
+where
+    T: Clone + Debug + Ord,
+    U: Clone + Debug + Ord,
+{
+    let mut expected = before
+        .iter()
+        .zip(after)
+        .filter(|((_, from), (_, to))| from != to)
+        .map(|((point, from), (_, to))| (point.clone(), from.clone(), to.clone()))
+        .collect::<Vec<(U, T, T)>>();
+    expected.sort();
+    migrations.sort();
+    assert_eq!(migrations, expected);
+}
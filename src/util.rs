@@ -5,9 +5,7 @@ where
     T: Hash,
     H: BuildHasher,
 {
-    let mut hasher = hash_builder.build_hasher();
-    value.hash(&mut hasher);
-    hasher.finish()
+    hash_builder.hash_one(value)
 }
 
 pub fn combine_hash<H>(hash_builder: &H, x: u64, y: u64) -> u64